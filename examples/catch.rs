@@ -0,0 +1,80 @@
+extern crate llvm;
+extern crate libc;
+use libc::{c_char, c_int};
+use llvm::*;
+
+const EXCEPTION_CLASS: u64 = 0x4c4c_564d_2d52_5300;
+
+#[repr(C)]
+struct UnwindException {
+    class: u64,
+    cleanup: extern fn(c_int, *mut UnwindException),
+    private: [u64; 2]
+}
+
+#[link(name = "stdc++")]
+extern "C-unwind" {
+    fn _Unwind_RaiseException(exception: *mut UnwindException) -> c_int;
+}
+
+#[link(name = "stdc++")]
+extern {
+    fn __gxx_personality_v0();
+    fn __cxa_begin_catch(exception: *mut c_char) -> *mut c_char;
+    fn __cxa_end_catch();
+}
+
+extern fn cleanup(_: c_int, exception: *mut UnwindException) {
+    unsafe { drop(Box::from_raw(exception)) }
+}
+
+/// Unwinds through the JIT-compiled code that calls it.
+extern "C-unwind" fn throw_from_rust() {
+    let exception = Box::new(UnwindException {
+        class: EXCEPTION_CLASS,
+        cleanup: cleanup,
+        private: [0; 2]
+    });
+    unsafe { _Unwind_RaiseException(Box::into_raw(exception)) };
+}
+
+fn main() {
+    let ctx = Context::new();
+    let module = Module::new("catch", &ctx);
+    let throw = module.add_function("throw_from_rust", Type::get::<fn() -> ()>(&ctx));
+    let personality = module.add_function("__gxx_personality_v0", Type::get::<fn() -> i32>(&ctx));
+    let begin_catch = module.add_function("__cxa_begin_catch", Type::get::<fn(*const c_char) -> *const c_char>(&ctx));
+    let end_catch = module.add_function("__cxa_end_catch", Type::get::<fn() -> ()>(&ctx));
+    let func = module.add_function("catches", Type::get::<fn() -> bool>(&ctx));
+    func.set_personality(personality);
+    let entry = func.append("entry");
+    let returned = func.append("returned");
+    let unwound = func.append("unwound");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_invoke(throw, &[], returned, unwound);
+    builder.position_at_end(returned);
+    builder.build_ret(false.compile(&ctx));
+    builder.position_at_end(unwound);
+    let ty = StructType::new(&ctx, &[Type::get::<*const c_char>(&ctx), Type::get::<i32>(&ctx)], false);
+    let landing_pad = builder.build_landing_pad(ty, None);
+    landing_pad.add_catch_all();
+    let exception = builder.build_extract_value(landing_pad, 0);
+    builder.build_call(begin_catch, &[exception]);
+    builder.build_call(end_catch, &[]);
+    builder.build_ret(true.compile(&ctx));
+    println!("{:?}", module);
+    module.verify().unwrap();
+    let ee = JitEngine::new(&module, JitOptions {opt_level: 0}).unwrap();
+    unsafe {
+        ee.add_global_mapping(throw, throw_from_rust as *const u8);
+        ee.add_global_mapping(personality, __gxx_personality_v0 as *const u8);
+        ee.add_global_mapping(begin_catch, __cxa_begin_catch as *const u8);
+        ee.add_global_mapping(end_catch, __cxa_end_catch as *const u8);
+    }
+    unsafe {
+        ee.with_function_unchecked(func, |catches: extern fn(()) -> bool| {
+            println!("caught = {}", catches(()));
+        });
+    }
+}
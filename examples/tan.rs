@@ -4,8 +4,9 @@ use llvm::Attribute::*;
 fn main() {
     let ctx = Context::new();
     let module = Module::new("simple", &ctx);
-    let cos = module.add_function("llvm.cos.f64", Type::get::<fn(f64) -> f64>(&ctx));
-    let sin = module.add_function("llvm.sin.f64", Type::get::<fn(f64) -> f64>(&ctx));
+    let f64_t = Type::get::<f64>(&ctx);
+    let cos = module.get_intrinsic(Intrinsic::Cos, &[f64_t]);
+    let sin = module.get_intrinsic(Intrinsic::Sin, &[f64_t]);
     let func = module.add_function("tan", Type::get::<fn(f64) -> f64>(&ctx));
     func.add_attributes(&[NoUnwind, ReadNone]);
     let entry = func.append("entry");
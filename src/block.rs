@@ -4,7 +4,7 @@ use std::iter::{Iterator, DoubleEndedIterator, IntoIterator};
 use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
-use value::{Function, Value};
+use value::{Function, Instruction, Value};
 use util::{self, Sub};
 
 /// A container of instructions that execute sequentially.
@@ -32,15 +32,15 @@ impl BasicBlock {
         unsafe { util::ptr_to_null(core::LLVMGetBasicBlockParent(self.into())) }
     }
     /// Return the terminator instruction for this basic block.
-    pub fn get_terminator(&self) -> Option<&Value> {
+    pub fn get_terminator(&self) -> Option<&Instruction> {
         unsafe { util::ptr_to_null(core::LLVMGetBasicBlockTerminator(self.into())) }
     }
     /// Return the first instruction for this basic block.
-    pub fn get_first(&self) -> Option<&Value> {
+    pub fn get_first(&self) -> Option<&Instruction> {
         unsafe { util::ptr_to_null(core::LLVMGetFirstInstruction(self.into())) }
     }
     /// Return the last instruction for this basic block.
-    pub fn get_last(&self) -> Option<&Value> {
+    pub fn get_last(&self) -> Option<&Instruction> {
         unsafe { util::ptr_to_null(core::LLVMGetLastInstruction(self.into())) }
     }
     /// Move this basic block after the `other` basic block in its function.
@@ -66,14 +66,16 @@ impl BasicBlock {
 
 /// Iterates through all the blocks contained in a function.
 pub struct BlockIter<'a> {
-    pub min: &'a BasicBlock,
-    pub max: &'a BasicBlock
+    pub min: Option<&'a BasicBlock>,
+    pub max: Option<&'a BasicBlock>
 }
 impl<'a> BlockIter<'a> {
     pub fn new(function: &'a Function) -> BlockIter<'a> {
-        BlockIter {
-            min: unsafe { core::LLVMGetFirstBasicBlock(function.into()).into() },
-            max: unsafe { core::LLVMGetLastBasicBlock(function.into()).into() }
+        unsafe {
+            BlockIter {
+                min: util::ptr_to_null(core::LLVMGetFirstBasicBlock(function.into())),
+                max: util::ptr_to_null(core::LLVMGetLastBasicBlock(function.into()))
+            }
         }
     }
 }
@@ -88,27 +90,114 @@ impl<'a> IntoIterator for &'a Function {
 impl<'a> Iterator for BlockIter<'a> {
     type Item = &'a BasicBlock;
     fn next(&mut self) -> Option<&'a BasicBlock> {
+        let block = self.min;
         if self.min == self.max {
-            None
-        } else {
-            unsafe {
-                let _block = self.min;
-                self.min = core::LLVMGetNextBasicBlock(self.min.into()).into();
-                Some(_block)
-            }
+            self.min = None;
+            self.max = None;
+        } else if let Some(min) = self.min {
+            self.min = unsafe { util::ptr_to_null(core::LLVMGetNextBasicBlock(min.into())) };
         }
+        block
     }
 }
 impl<'a> DoubleEndedIterator for BlockIter<'a> {
     fn next_back(&mut self) -> Option<&'a BasicBlock> {
+        let block = self.max;
         if self.min == self.max {
-            None
-        } else {
-            unsafe {
-                let _block = self.max;
-                self.max = core::LLVMGetPreviousBasicBlock(self.max.into()).into();
-                Some(_block)
+            self.min = None;
+            self.max = None;
+        } else if let Some(max) = self.max {
+            self.max = unsafe { util::ptr_to_null(core::LLVMGetPreviousBasicBlock(max.into())) };
+        }
+        block
+    }
+}
+
+/// Iterates through all the instructions contained in a basic block.
+pub struct InstrIter<'a> {
+    pub min: Option<&'a Instruction>,
+    pub max: Option<&'a Instruction>
+}
+impl<'a> InstrIter<'a> {
+    pub fn new(block: &'a BasicBlock) -> InstrIter<'a> {
+        InstrIter {
+            min: block.get_first(),
+            max: block.get_last()
+        }
+    }
+}
+impl<'a> IntoIterator for &'a BasicBlock {
+    type IntoIter = InstrIter<'a>;
+    type Item = &'a Instruction;
+    fn into_iter(self) -> InstrIter<'a> {
+        InstrIter::new(self)
+    }
+}
+impl<'a> Iterator for InstrIter<'a> {
+    type Item = &'a Instruction;
+    fn next(&mut self) -> Option<&'a Instruction> {
+        let instr = self.min;
+        if self.min == self.max {
+            self.min = None;
+            self.max = None;
+        } else if let Some(min) = self.min {
+            self.min = min.get_next();
+        }
+        instr
+    }
+}
+impl<'a> DoubleEndedIterator for InstrIter<'a> {
+    fn next_back(&mut self) -> Option<&'a Instruction> {
+        let instr = self.max;
+        if self.min == self.max {
+            self.min = None;
+            self.max = None;
+        } else if let Some(max) = self.max {
+            self.max = max.get_previous();
+        }
+        instr
+    }
+}
+
+/// Iterates through all the instructions contained in a function, block by block.
+pub struct FunctionInstrIter<'a> {
+    blocks: BlockIter<'a>,
+    front: Option<InstrIter<'a>>,
+    back: Option<InstrIter<'a>>
+}
+impl<'a> FunctionInstrIter<'a> {
+    pub fn new(function: &'a Function) -> FunctionInstrIter<'a> {
+        FunctionInstrIter {
+            blocks: BlockIter::new(function),
+            front: None,
+            back: None
+        }
+    }
+}
+impl<'a> Iterator for FunctionInstrIter<'a> {
+    type Item = &'a Instruction;
+    fn next(&mut self) -> Option<&'a Instruction> {
+        loop {
+            if let Some(instr) = self.front.as_mut().and_then(|instrs| instrs.next()) {
+                return Some(instr);
+            }
+            match self.blocks.next() {
+                Some(block) => self.front = Some(InstrIter::new(block)),
+                None => return self.back.as_mut().and_then(|instrs| instrs.next())
+            }
+        }
+    }
+}
+impl<'a> DoubleEndedIterator for FunctionInstrIter<'a> {
+    fn next_back(&mut self) -> Option<&'a Instruction> {
+        loop {
+            if let Some(instr) = self.back.as_mut().and_then(|instrs| instrs.next_back()) {
+                return Some(instr);
+            }
+            match self.blocks.next_back() {
+                Some(block) => self.back = Some(InstrIter::new(block)),
+                None => return self.front.as_mut().and_then(|instrs| instrs.next_back())
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -1,17 +1,28 @@
 use libc::{c_char, c_uint};
 use ffi::prelude::{LLVMBuilderRef, LLVMValueRef};
-use ffi::{core, LLVMBuilder, LLVMRealPredicate, LLVMIntPredicate};
+use ffi::{core, LLVMBuilder, LLVMTypeKind};
 use cbox::CSemiBox;
 use std::marker::PhantomData;
 use std::mem;
 use block::BasicBlock;
-use context::Context;
-use types::Type;
-use value::{Function, Value, Predicate};
+use compile::Compile;
+use context::{Context, GetContext};
+use intrinsic::Intrinsic;
+use module::Module;
+use types::{ArrayType, FunctionType, IntegerType, PointerType, StructType, Type, VectorType};
+use util::{self, Sub};
+use value::{AtomicBinOp, AtomicOrdering, CallConv, Function, IndirectBr, InlineAsm, Instruction, LandingPad, Phi, Value, Predicate, Switch};
 
 static NULL_NAME:[c_char; 1] = [0];
 
 /// This provides a uniform API for creating instructions and inserting them into a basic block.
+///
+/// The values it builds are unnamed, so they can be named with `Value::named` to make the
+/// printed IR more readable.
+///
+/// Arithmetic flags are available through `build_nsw_add`, `build_nuw_mul`, `build_exact_div`
+/// and the like. The LLVM C API has no way to set fast-math flags or to mark unsigned divisions
+/// and shifts as `exact`, so these can't be built.
 pub struct Builder(PhantomData<[u8]>);
 native_ref!(&Builder = LLVMBuilderRef);
 dispose!{Builder, LLVMBuilder, core::LLVMDisposeBuilder}
@@ -41,7 +52,78 @@ macro_rules! un_op(
         }
     );
 );
+macro_rules! cast_op(
+    ($(#[$attr:meta])* $name:ident, $func:ident) => (
+        $(#[$attr])*
+        pub fn $name(&self, value: &Value, dest: &Type) -> &Value {
+            unsafe { core::$func(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()) }.into()
+        }
+    );
+);
+/// Returns the width of the floating-point type `ty` in bits.
+fn float_width(ty: &Type) -> usize {
+    match unsafe { core::LLVMGetTypeKind(ty.into()) } {
+        LLVMTypeKind::LLVMHalfTypeKind => 16,
+        LLVMTypeKind::LLVMFloatTypeKind => 32,
+        LLVMTypeKind::LLVMDoubleTypeKind => 64,
+        LLVMTypeKind::LLVMX86_FP80TypeKind => 80,
+        _ => 128
+    }
+}
+/// Returns the element type of `ty` if it is a vector, or `ty` otherwise.
+fn scalar_type(ty: &Type) -> &Type {
+    unsafe {
+        if core::LLVMGetTypeKind(ty.into()) as c_uint == LLVMTypeKind::LLVMVectorTypeKind as c_uint {
+            core::LLVMGetElementType(ty.into()).into()
+        } else {
+            ty
+        }
+    }
+}
+/// Returns the number of elements in `ty` if it is a vector type.
+fn vector_length(ty: &Type) -> Option<usize> {
+    VectorType::from_super(ty).map(|vec| vec.get_size())
+}
 impl Builder {
+    /// Returns the declaration of the intrinsic `intrinsic` in the module the builder is
+    /// positioned in.
+    fn get_intrinsic<'a>(&'a self, intrinsic: Intrinsic, types: &[&'a Type]) -> &'a Function {
+        let block = self.get_insert_block().expect("builder is not positioned in a block");
+        let func = block.get_parent().expect("block is not in a function");
+        let module:&Module = unsafe { core::LLVMGetGlobalParent(func.into()) }.into();
+        module.get_intrinsic(intrinsic, types)
+    }
+    /// Build an instruction that bitcasts `ptr` into an `i8*` in the same address space if it
+    /// isn't one already.
+    fn build_byte_ptr<'a>(&'a self, ptr: &'a Value) -> &'a Value {
+        let ty = ptr.get_type();
+        assert!(ty.is_pointer(), "expected a pointer, got {:?}", ty);
+        let byte_ptr: &Type = unsafe {
+            let space = core::LLVMGetPointerAddressSpace(ty.into());
+            core::LLVMPointerType(Type::get::<i8>(ty.get_context()).into(), space).into()
+        };
+        self.build_cast(ptr, byte_ptr, false)
+    }
+    /// Build an instruction that casts `ptr` into an `i8*` in the generic address space if it
+    /// isn't one already, for the intrinsics that aren't overloaded on pointer types.
+    fn build_generic_byte_ptr<'a>(&'a self, ptr: &'a Value) -> &'a Value {
+        let byte_ptr = PointerType::new(Type::get::<i8>(ptr.get_context()));
+        self.build_cast(ptr, byte_ptr, false)
+    }
+    /// Build a call to the memory intrinsic `intrinsic` on `dest` with the value `val`, which
+    /// is either a pointer or a byte.
+    fn build_mem_intrinsic(&self, intrinsic: Intrinsic, dest: &Value, val: &Value, len: &Value, align: usize, volatile: bool) -> &Value {
+        let ctx = dest.get_context();
+        let dest = self.build_byte_ptr(dest);
+        let (types, val) = if val.get_type().is_pointer() {
+            let val = self.build_byte_ptr(val);
+            (vec![dest.get_type(), val.get_type(), len.get_type()], val)
+        } else {
+            (vec![dest.get_type(), len.get_type()], val)
+        };
+        let func = self.get_intrinsic(intrinsic, &types);
+        self.build_call(func, &[dest, val, len, (align as u32).compile(ctx), volatile.compile(ctx)])
+    }
     /// Create a new builder in the context given.
     pub fn new(context: &Context) -> CSemiBox<Builder> {
         CSemiBox::new(unsafe { core::LLVMCreateBuilderInContext(context.into()) }.into())
@@ -50,6 +132,57 @@ impl Builder {
     pub fn position_at_end(&self, block: &BasicBlock) {
         unsafe { core::LLVMPositionBuilderAtEnd(self.into(), block.into()) }
     }
+    /// Position the builder before the instruction `instr` in the block `block`.
+    ///
+    /// This panics if `instr` isn't in `block`.
+    pub fn position_at(&self, block: &BasicBlock, instr: &Instruction) {
+        assert!(instr.get_parent() == Some(block), "instruction {:?} is not in the block given", instr);
+        unsafe { core::LLVMPositionBuilder(self.into(), block.into(), instr.into()) }
+    }
+    /// Position the builder before the instruction `instr`.
+    ///
+    /// This panics if `instr` isn't in a basic block.
+    pub fn position_before(&self, instr: &Instruction) {
+        assert!(instr.get_parent().is_some(), "instruction {:?} is not in a basic block", instr);
+        unsafe { core::LLVMPositionBuilderBefore(self.into(), instr.into()) }
+    }
+    /// Returns the block the builder is positioned in, or `None` if it isn't positioned anywhere.
+    pub fn get_insert_block(&self) -> Option<&BasicBlock> {
+        unsafe { util::ptr_to_null(core::LLVMGetInsertBlock(self.into())) }
+    }
+    /// Clear the position of the builder, so it won't insert instructions anywhere until it
+    /// is positioned again.
+    pub fn clear_insertion_position(&self) {
+        unsafe { core::LLVMClearInsertionPosition(self.into()) }
+    }
+    /// Insert the instruction `instr`, which must not be in a basic block, at the position
+    /// of the builder.
+    pub fn insert(&self, instr: &Instruction) {
+        assert!(instr.get_parent().is_none(), "instruction {:?} is already in a basic block", instr);
+        unsafe { core::LLVMInsertIntoBuilder(self.into(), instr.into()) }
+    }
+    /// Save the position of the builder, and return a guard that positions the builder
+    /// there again when it is dropped.
+    ///
+    /// This is useful for emitting code somewhere else temporarily, such as adding an
+    /// `alloca` to the entry block of a function. The instruction the builder is positioned
+    /// before, if any, must not be removed while the guard is alive.
+    pub fn save_position(&self) -> PositionGuard {
+        let block = self.get_insert_block();
+        // The C API can't tell which instruction the builder is positioned before, so find
+        // it by inserting a placeholder there and deleting it again.
+        let next = block.and_then(|_| unsafe {
+            let marker = core::LLVMBuildUnreachable(self.into());
+            let next = util::ptr_to_null(core::LLVMGetNextInstruction(marker));
+            core::LLVMInstructionEraseFromParent(marker);
+            next
+        });
+        PositionGuard {
+            builder: self,
+            block: block,
+            next: next
+        }
+    }
     /// Build an instruction that returns from the function with void.
     pub fn build_ret_void(&self) -> &Value {
         unsafe { core::LLVMBuildRetVoid(self.into()) }.into()
@@ -68,15 +201,125 @@ impl Builder {
     pub fn build_alloca(&self, ty: &Type) -> &Value {
         unsafe { core::LLVMBuildAlloca(self.into(), ty.into(), NULL_NAME.as_ptr() as *const c_char) }.into()
     }
+    /// Build an instruction that allocates a pointer on the heap to fit the size of `ty` then
+    /// returns this pointer.
+    ///
+    /// This should be freed with `build_free` once it is no longer needed.
+    pub fn build_malloc(&self, ty: &Type) -> &Value {
+        unsafe { core::LLVMBuildMalloc(self.into(), ty.into(), NULL_NAME.as_ptr()) }.into()
+    }
+    /// Build an instruction that allocates an array on the heap with the element type `elem`
+    /// and the size `size`.
+    ///
+    /// This should be freed with `build_free` once it is no longer needed.
+    pub fn build_array_malloc(&self, elem: &Type, size: &Value) -> &Value {
+        unsafe { core::LLVMBuildArrayMalloc(self.into(), elem.into(), size.into(), NULL_NAME.as_ptr()) }.into()
+    }
     /// Build an instruction that frees the `val`, which _MUST_ be a pointer that was returned
-    /// from `build_alloca`.
+    /// from `build_malloc` or `build_array_malloc`.
     pub fn build_free(&self, val: &Value) -> &Value {
         unsafe { core::LLVMBuildFree(self.into(), val.into()) }.into()
     }
+    /// Build a call to `llvm.memcpy` that copies `len` bytes from the pointer `src` to the
+    /// pointer `dest`, which must not overlap.
+    ///
+    /// Both pointers must be aligned to `align` bytes, and the intrinsic is declared in the
+    /// module if it hasn't been already.
+    pub fn build_memcpy(&self, dest: &Value, src: &Value, len: &Value, align: usize, volatile: bool) -> &Value {
+        self.build_mem_intrinsic(Intrinsic::Memcpy, dest, src, len, align, volatile)
+    }
+    /// Build a call to `llvm.memmove` that copies `len` bytes from the pointer `src` to the
+    /// pointer `dest`, which may overlap.
+    ///
+    /// Both pointers must be aligned to `align` bytes, and the intrinsic is declared in the
+    /// module if it hasn't been already.
+    pub fn build_memmove(&self, dest: &Value, src: &Value, len: &Value, align: usize, volatile: bool) -> &Value {
+        self.build_mem_intrinsic(Intrinsic::Memmove, dest, src, len, align, volatile)
+    }
+    /// Build a call to `llvm.memset` that fills `len` bytes from the pointer `dest` with the
+    /// byte `val`.
+    ///
+    /// The pointer must be aligned to `align` bytes, and the intrinsic is declared in the
+    /// module if it hasn't been already.
+    pub fn build_memset(&self, dest: &Value, val: &Value, len: &Value, align: usize, volatile: bool) -> &Value {
+        assert_eq!(val.get_type(), Type::get::<u8>(val.get_context()));
+        self.build_mem_intrinsic(Intrinsic::Memset, dest, val, len, align, volatile)
+    }
+    /// Build a call to `llvm.lifetime.start` that marks the start of the lifetime of the
+    /// `size` bytes in the pointer `ptr`.
+    pub fn build_lifetime_start(&self, ptr: &Value, size: u64) -> &Value {
+        self.build_lifetime_marker(Intrinsic::LifetimeStart, ptr, size)
+    }
+    /// Build a call to `llvm.lifetime.end` that marks the end of the lifetime of the
+    /// `size` bytes in the pointer `ptr`.
+    pub fn build_lifetime_end(&self, ptr: &Value, size: u64) -> &Value {
+        self.build_lifetime_marker(Intrinsic::LifetimeEnd, ptr, size)
+    }
+    fn build_lifetime_marker(&self, intrinsic: Intrinsic, ptr: &Value, size: u64) -> &Value {
+        let ptr = self.build_generic_byte_ptr(ptr);
+        let func = self.get_intrinsic(intrinsic, &[]);
+        self.build_call(func, &[size.compile(ptr.get_context()), ptr])
+    }
     /// Build an instruction that store the value `val` in the pointer `ptr`.
     pub fn build_store(&self, val: &Value, ptr: &Value) -> &Value {
         unsafe { core::LLVMBuildStore(self.into(), val.into(), ptr.into()) }.into()
     }
+    /// Build an instruction that atomically applies `op` to the value in the pointer `ptr`
+    /// and `val`, then stores the result in `ptr`.
+    ///
+    /// This will return the value that was in `ptr` before.
+    pub fn build_atomic_rmw(&self, op: AtomicBinOp, ptr: &Value, val: &Value, ordering: AtomicOrdering) -> &Value {
+        unsafe { core::LLVMBuildAtomicRMW(self.into(), op.into(), ptr.into(), val.into(), ordering.into(), 0) }.into()
+    }
+    /// Build an instruction that atomically stores `new` in the pointer `ptr` if the value
+    /// in it is equal to `cmp`.
+    ///
+    /// This will return a struct of the value that was in `ptr` before and a boolean that
+    /// is true if the store happened. The `failure` ordering is used when it didn't, so it
+    /// can't be `Release` or `AcquireRelease`, and can't be stronger than `success`. Both
+    /// orderings must be at least `Monotonic`.
+    ///
+    /// This panics if the orderings given break these rules.
+    pub fn build_cmpxchg(&self, ptr: &Value, cmp: &Value, new: &Value, success: AtomicOrdering, failure: AtomicOrdering) -> &Value {
+        let valid = match failure {
+            AtomicOrdering::Monotonic => true,
+            AtomicOrdering::Acquire => success == AtomicOrdering::Acquire || success == AtomicOrdering::AcquireRelease || success == AtomicOrdering::SequentiallyConsistent,
+            AtomicOrdering::SequentiallyConsistent => success == AtomicOrdering::SequentiallyConsistent,
+            _ => false
+        } && success != AtomicOrdering::NotAtomic && success != AtomicOrdering::Unordered;
+        assert!(valid, "invalid cmpxchg orderings {:?} on success and {:?} on failure", success, failure);
+        unsafe { core::LLVMBuildAtomicCmpXchg(self.into(), ptr.into(), cmp.into(), new.into(), success.into(), failure.into(), 0) }.into()
+    }
+    /// Build an instruction that introduces the ordering constraints given between memory accesses.
+    pub fn build_fence(&self, ordering: AtomicOrdering) -> &Value {
+        unsafe { core::LLVMBuildFence(self.into(), ordering.into(), 0, NULL_NAME.as_ptr()) }.into()
+    }
+    /// Build an instruction that reads the next argument of the type `ty` from the argument
+    /// list `list`, which must have been initialised with `build_va_start`.
+    pub fn build_va_arg(&self, list: &Value, ty: &Type) -> &Value {
+        unsafe { core::LLVMBuildVAArg(self.into(), list.into(), ty.into(), NULL_NAME.as_ptr()) }.into()
+    }
+    /// Build a call to `llvm.va_start` that initialises the argument list `list` to the
+    /// variable arguments of the current function.
+    ///
+    /// This should be a pointer to memory the size of the target's `va_list`, such as one
+    /// returned by `build_alloca`, since `llvm.va_start` writes the whole `va_list` into it.
+    /// This size differs between targets, for example x86-64 uses a 24-byte
+    /// `[1 x {i32, i32, i8*, i8*}]` while some other targets use a single `i8*`.
+    pub fn build_va_start(&self, list: &Value) -> &Value {
+        let list = self.build_generic_byte_ptr(list);
+        self.build_call(self.get_intrinsic(Intrinsic::VaStart, &[]), &[list])
+    }
+    /// Build a call to `llvm.va_end` that destroys the argument list `list`.
+    pub fn build_va_end(&self, list: &Value) -> &Value {
+        let list = self.build_generic_byte_ptr(list);
+        self.build_call(self.get_intrinsic(Intrinsic::VaEnd, &[]), &[list])
+    }
+    /// Build a call to `llvm.va_copy` that copies the argument list `src` into `dest`.
+    pub fn build_va_copy(&self, dest: &Value, src: &Value) -> &Value {
+        let (dest, src) = (self.build_generic_byte_ptr(dest), self.build_generic_byte_ptr(src));
+        self.build_call(self.get_intrinsic(Intrinsic::VaCopy, &[]), &[dest, src])
+    }
     /// Build an instruction that branches to the block `dest`.
     pub fn build_br(&self, dest: &BasicBlock) -> &Value {
         unsafe { core::LLVMBuildBr(self.into(), dest.into()).into() }
@@ -105,25 +348,178 @@ impl Builder {
             call.into()
         }
     }
+    /// Build a PHI node with the type `ty`, which should have its incoming values added
+    /// with `Phi::add_incoming`.
+    pub fn build_phi(&self, ty: &Type) -> &Phi {
+        unsafe { core::LLVMBuildPhi(self.into(), ty.into(), NULL_NAME.as_ptr()).into() }
+    }
+    /// Build an instruction that calls the function pointer `callee`, which has the signature
+    /// `sig`, with the arguments `args` and the calling convention `conv`.
+    ///
+    /// If `callee` isn't a pointer to `sig` already, it will be cast into one first, so it
+    /// can also be another kind of pointer or an integer address.
+    ///
+    /// If `sig` is variadic, any arguments after its fixed parameters are passed as the
+    /// variable arguments.
+    ///
+    /// This will return the return value of the function.
+    pub fn build_call_indirect(&self, callee: &Value, sig: &FunctionType, args: &[&Value], conv: CallConv) -> &Value {
+        let params = sig.get_params();
+        if sig.is_variadic() {
+            assert!(args.len() >= params.len(), "expected at least {} arguments, got {}", params.len(), args.len());
+        } else {
+            assert_eq!(params.len(), args.len());
+        }
+        for (param, arg) in params.iter().zip(args) {
+            assert_eq!(*param, arg.get_type());
+        }
+        let callee = self.build_cast(callee, PointerType::new(sig), false);
+        unsafe {
+            let call = core::LLVMBuildCall(self.into(), callee.into(), args.as_ptr() as *mut LLVMValueRef, args.len() as c_uint, NULL_NAME.as_ptr());
+            core::LLVMSetInstructionCallConv(call, conv as c_uint);
+            call.into()
+        }
+    }
+    /// Build an instruction that runs the inline assembly `asm` with the arguments `args`.
+    ///
+    /// This will return the result of the assembly.
+    pub fn build_call_asm(&self, asm: &InlineAsm, args: &[&Value]) -> &Value {
+        self.build_call_indirect(asm, asm.get_signature(), args, CallConv::C)
+    }
+    /// Build an instruction that calls the function `func` with the arguments `args`, then
+    /// continues at `normal` if it returns or at `unwind` if it unwinds.
+    ///
+    /// This will return the return value of the function.
+    pub fn build_invoke(&self, func: &Function, args: &[&Value], normal: &BasicBlock, unwind: &BasicBlock) -> &Value {
+        unsafe { core::LLVMBuildInvoke(self.into(), func.into(), args.as_ptr() as *mut LLVMValueRef, args.len() as c_uint, normal.into(), unwind.into(), NULL_NAME.as_ptr()).into() }
+    }
+    /// Build a landing pad that yields a value of the type `ty` describing the exception
+    /// being unwound, which should have its clauses added with `LandingPad::add_catch` etc.
+    ///
+    /// If `personality` is given, it will be set as the personality of the enclosing function.
+    pub fn build_landing_pad(&self, ty: &Type, personality: Option<&Function>) -> &LandingPad {
+        unsafe { core::LLVMBuildLandingPad(self.into(), ty.into(), mem::transmute(personality), 0, NULL_NAME.as_ptr()).into() }
+    }
+    /// Build an instruction that resumes unwinding the exception `value` that was caught by a
+    /// landing pad.
+    pub fn build_resume(&self, value: &Value) -> &Value {
+        unsafe { core::LLVMBuildResume(self.into(), value.into()).into() }
+    }
     /// Build an instruction that yields to `true_val` if `cond` is equal to `1`, and `false_val` otherwise.
     pub fn build_select(&self, cond: &Value, true_val: &Value, false_val: &Value) -> &Value {
         unsafe { core::LLVMBuildSelect(self.into(), cond.into(), true_val.into(), false_val.into(), NULL_NAME.as_ptr()).into() }
     }
-    /// Build an instruction that casts a value into a certain type.
-    pub fn build_bit_cast(&self, value: &Value, dest: &Type) -> &Value {
-        unsafe { core::LLVMBuildBitCast(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()).into() }
+    cast_op!{
+        /// Build an instruction that casts a value into a certain type without changing any bits.
+        build_bit_cast, LLVMBuildBitCast
+    }
+    cast_op!{
+        /// Build an instruction to bitcast in integer into a pointer.
+        build_int_to_ptr, LLVMBuildIntToPtr
+    }
+    cast_op!{
+        /// Build an instruction that converts a pointer into an integer.
+        build_ptr_to_int, LLVMBuildPtrToInt
+    }
+    cast_op!{
+        /// Build an instruction that zero extends its operand to the type `dest`.
+        build_zext, LLVMBuildZExt
+    }
+    cast_op!{
+        /// Build an instruction that sign extends its operand to the type `dest`.
+        build_sext, LLVMBuildSExt
+    }
+    cast_op!{
+        /// Build an instruction that truncates the high-order bits of value to fit into a certain type.
+        build_trunc, LLVMBuildTrunc
+    }
+    cast_op!{
+        /// Build an instruction that truncates a floating-point value to a smaller floating-point type.
+        build_fp_trunc, LLVMBuildFPTrunc
+    }
+    cast_op!{
+        /// Build an instruction that extends a floating-point value to a larger floating-point type.
+        build_fp_ext, LLVMBuildFPExt
     }
-    /// Build an instruction to bitcast in integer into a pointer.
-    pub fn build_int_to_ptr(&self, value: &Value, dest: &Type) -> &Value {
-        unsafe { core::LLVMBuildIntToPtr(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()).into() }
+    cast_op!{
+        /// Build an instruction that converts a floating-point value into an unsigned integer.
+        build_fp_to_ui, LLVMBuildFPToUI
     }
-    /// Build an instruction that zero extends its operand to the type `dest`.
-    pub fn build_zext(&self, value: &Value, dest: &Type) -> &Value {
-        unsafe { core::LLVMBuildZExtOrBitCast(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()).into() }
+    cast_op!{
+        /// Build an instruction that converts a floating-point value into a signed integer.
+        build_fp_to_si, LLVMBuildFPToSI
     }
-    /// Build an instruction that truncates the high-order bits of value to fit into a certain type.
-    pub fn build_trunc(&self, value: &Value, dest: &Type) -> &Value {
-        unsafe { core::LLVMBuildTrunc(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()).into() }
+    cast_op!{
+        /// Build an instruction that converts an unsigned integer into a floating-point value.
+        build_ui_to_fp, LLVMBuildUIToFP
+    }
+    cast_op!{
+        /// Build an instruction that converts a signed integer into a floating-point value.
+        build_si_to_fp, LLVMBuildSIToFP
+    }
+    cast_op!{
+        /// Build an instruction that converts a pointer into a pointer in a different address space.
+        build_address_space_cast, LLVMBuildAddrSpaceCast
+    }
+    /// Build whichever cast instruction converts `value` into the type `dest`, based on the
+    /// kinds of both types.
+    ///
+    /// Integers are treated as signed when `signed` is true and unsigned otherwise. If `value`
+    /// already has the type `dest`, it is returned as is. This panics if there is no conversion
+    /// between the types, such as when they are vectors of different lengths.
+    pub fn build_cast<'a>(&'a self, value: &'a Value, dest: &Type, signed: bool) -> &'a Value {
+        let src = value.get_type();
+        if src == dest {
+            return value;
+        }
+        if vector_length(src) != vector_length(dest) {
+            panic!("cannot cast {:?} to {:?}", src, dest)
+        }
+        let (st, dt) = (scalar_type(src), scalar_type(dest));
+        if st.is_integer() && dt.is_integer() {
+            let (sw, dw) = (IntegerType::from_super(st).unwrap().get_width(), IntegerType::from_super(dt).unwrap().get_width());
+            if sw > dw {
+                self.build_trunc(value, dest)
+            } else if signed {
+                self.build_sext(value, dest)
+            } else {
+                self.build_zext(value, dest)
+            }
+        } else if st.is_float() && dt.is_float() {
+            let (sw, dw) = (float_width(st), float_width(dt));
+            if sw > dw {
+                self.build_fp_trunc(value, dest)
+            } else if sw < dw {
+                self.build_fp_ext(value, dest)
+            } else {
+                panic!("cannot cast {:?} to {:?}", src, dest)
+            }
+        } else if st.is_integer() && dt.is_float() {
+            if signed {
+                self.build_si_to_fp(value, dest)
+            } else {
+                self.build_ui_to_fp(value, dest)
+            }
+        } else if st.is_float() && dt.is_integer() {
+            if signed {
+                self.build_fp_to_si(value, dest)
+            } else {
+                self.build_fp_to_ui(value, dest)
+            }
+        } else if st.is_pointer() && dt.is_integer() {
+            self.build_ptr_to_int(value, dest)
+        } else if st.is_integer() && dt.is_pointer() {
+            self.build_int_to_ptr(value, dest)
+        } else if st.is_pointer() && dt.is_pointer() {
+            let (sa, da) = unsafe { (core::LLVMGetPointerAddressSpace(st.into()), core::LLVMGetPointerAddressSpace(dt.into())) };
+            if sa == da {
+                self.build_bit_cast(value, dest)
+            } else {
+                self.build_address_space_cast(value, dest)
+            }
+        } else {
+            panic!("cannot cast {:?} to {:?}", src, dest)
+        }
     }
     /// Build an instruction that inserts a value into an aggregate data value.
     pub fn build_insert_value(&self, agg: &Value, elem: &Value, index: usize) -> &Value {
@@ -133,59 +529,199 @@ impl Builder {
     pub fn build_extract_value(&self, agg: &Value, index: usize) -> &Value {
         unsafe { core::LLVMBuildExtractValue(self.into(), agg.into(), index as c_uint, NULL_NAME.as_ptr()).into() }
     }
+    /// Build an instruction that extracts the element at `index` from the vector `vector`.
+    pub fn build_extract_element(&self, vector: &Value, index: &Value) -> &Value {
+        unsafe { core::LLVMBuildExtractElement(self.into(), vector.into(), index.into(), NULL_NAME.as_ptr()).into() }
+    }
+    /// Build an instruction that inserts `elem` into the vector `vector` at `index`.
+    pub fn build_insert_element(&self, vector: &Value, elem: &Value, index: &Value) -> &Value {
+        unsafe { core::LLVMBuildInsertElement(self.into(), vector.into(), elem.into(), index.into(), NULL_NAME.as_ptr()).into() }
+    }
+    /// Build an instruction that makes a vector by picking elements from the vectors `a` and `b`
+    /// with the constant `mask`, which can be made with `Value::new_shuffle_mask`.
+    ///
+    /// Indices in the mask less than the length of `a` pick from `a`, and the rest pick from `b`.
+    pub fn build_shuffle_vector(&self, a: &Value, b: &Value, mask: &Value) -> &Value {
+        unsafe { core::LLVMBuildShuffleVector(self.into(), a.into(), b.into(), mask.into(), NULL_NAME.as_ptr()).into() }
+    }
+    /// Build instructions that make a vector that repeats `value` `length` times.
+    pub fn build_splat(&self, value: &Value, length: usize) -> &Value {
+        let ctx = value.get_context();
+        let vector_t = VectorType::new(value.get_type(), length);
+        let zero = 0u32.compile(ctx);
+        let vector = self.build_insert_element(Value::new_undef(vector_t), value, zero);
+        let mask = Value::new_shuffle_mask(ctx, &vec![0; length]);
+        self.build_shuffle_vector(vector, Value::new_undef(vector_t), mask)
+    }
     /// Build an instruction that computes the address of a subelement of an aggregate data structure.
     ///
     /// Basically type-safe pointer arithmetic.
     pub fn build_gep(&self, pointer: &Value, indices: &[&Value]) -> &Value {
+        unsafe { core::LLVMBuildGEP(self.into(), pointer.into(), indices.as_ptr() as *mut LLVMValueRef, indices.len() as c_uint, NULL_NAME.as_ptr()).into() }
+    }
+    /// Build an instruction that computes the address of a subelement of an aggregate data structure,
+    /// which must be inside the object `pointer` points to.
+    ///
+    /// The result is undefined if the address is out of the bounds of the object.
+    pub fn build_in_bounds_gep(&self, pointer: &Value, indices: &[&Value]) -> &Value {
         unsafe { core::LLVMBuildInBoundsGEP(self.into(), pointer.into(), indices.as_ptr() as *mut LLVMValueRef, indices.len() as c_uint, NULL_NAME.as_ptr()).into() }
     }
-    /// Build an instruction that runs whichever block matches the value, or `default` if none of them matched it.
-    pub fn build_switch(&self, value: &Value, default: &BasicBlock, cases: &[(&Value, &BasicBlock)]) -> &Value {
-        unsafe {
-            let switch = core::LLVMBuildSwitch(self.into(), value.into(), default.into(), cases.len() as c_uint);
-            for case in cases {
-                core::LLVMAddCase(switch, case.0.into(), case.1.into());
+    /// Build an instruction that computes the address of the field at `index` in the struct
+    /// `pointer` points to.
+    pub fn build_struct_gep(&self, pointer: &Value, index: usize) -> &Value {
+        let ty = PointerType::from_super(pointer.get_type()).expect("expected a pointer").get_element();
+        let fields = StructType::from_super(ty).expect("expected a pointer to a struct").get_elements();
+        assert!(index < fields.len(), "no such field {} on {:?}", index, ty);
+        unsafe { core::LLVMBuildStructGEP(self.into(), pointer.into(), index as c_uint, NULL_NAME.as_ptr()).into() }
+    }
+    /// Build an instruction that computes the address of a subelement of an aggregate data structure
+    /// from constant indices.
+    ///
+    /// The first index offsets `pointer` itself, and each index after it is checked to be in the
+    /// bounds of the struct, array or vector it indexes into.
+    pub fn build_gep_indices(&self, pointer: &Value, indices: &[usize]) -> &Value {
+        let ctx = pointer.get_context();
+        let mut ty = PointerType::from_super(pointer.get_type()).expect("expected a pointer").get_element();
+        let mut values = Vec::with_capacity(indices.len());
+        for (i, &index) in indices.iter().enumerate() {
+            if i == 0 {
+                values.push((index as u64).compile(ctx));
+            } else if let Some(st) = StructType::from_super(ty) {
+                let fields = st.get_elements();
+                assert!(index < fields.len(), "no such field {} on {:?}", index, ty);
+                values.push((index as u32).compile(ctx));
+                ty = fields[index];
+            } else if let Some(at) = ArrayType::from_super(ty) {
+                assert!(index < at.get_length(), "index {} is out of the bounds of {:?}", index, ty);
+                values.push((index as u64).compile(ctx));
+                ty = at.get_element();
+            } else if let Some(vt) = VectorType::from_super(ty) {
+                assert!(index < vt.get_size(), "index {} is out of the bounds of {:?}", index, ty);
+                values.push((index as u64).compile(ctx));
+                ty = vt.get_element();
+            } else {
+                panic!("can't index into {:?}", ty)
             }
-            switch.into()
         }
+        self.build_gep(pointer, &values)
+    }
+    /// Build an instruction that runs whichever block matches the value, or `default` if none of them matched it.
+    ///
+    /// More cases can be added to it later with `Switch::add_case`.
+    pub fn build_switch(&self, value: &Value, default: &BasicBlock, cases: &[(&Value, &BasicBlock)]) -> &Switch {
+        let switch:&Switch = unsafe { core::LLVMBuildSwitch(self.into(), value.into(), default.into(), cases.len() as c_uint) }.into();
+        switch.add_cases(cases);
+        switch
+    }
+    /// Build an instruction that branches to the block at the address `addr`, which must
+    /// be one of `dests`.
+    ///
+    /// More destinations can be added to it later with `IndirectBr::add_destination`.
+    pub fn build_indirect_br(&self, addr: &Value, dests: &[&BasicBlock]) -> &IndirectBr {
+        let br:&IndirectBr = unsafe { core::LLVMBuildIndirectBr(self.into(), addr.into(), dests.len() as c_uint) }.into();
+        for dest in dests {
+            br.add_destination(dest);
+        }
+        br
+    }
+    /// Build an instruction that marks the end of the block as unreachable.
+    pub fn build_unreachable(&self) -> &Value {
+        unsafe { core::LLVMBuildUnreachable(self.into()) }.into()
     }
     un_op!{build_load, LLVMBuildLoad}
     un_op!{build_neg, LLVMBuildNeg}
     un_op!{build_not, LLVMBuildNot}
+    un_op!{build_nsw_neg, LLVMBuildNSWNeg}
+    un_op!{build_nuw_neg, LLVMBuildNUWNeg}
     bin_op!{build_add, LLVMBuildAdd, LLVMBuildFAdd}
+    bin_op!{build_nsw_add, LLVMBuildNSWAdd}
+    bin_op!{build_nuw_add, LLVMBuildNUWAdd}
     bin_op!{build_sub, LLVMBuildSub, LLVMBuildFSub}
+    bin_op!{build_nsw_sub, LLVMBuildNSWSub}
+    bin_op!{build_nuw_sub, LLVMBuildNUWSub}
     bin_op!{build_mul, LLVMBuildMul, LLVMBuildFMul}
+    bin_op!{build_nsw_mul, LLVMBuildNSWMul}
+    bin_op!{build_nuw_mul, LLVMBuildNUWMul}
     bin_op!{build_div, LLVMBuildSDiv, LLVMBuildFDiv}
+    bin_op!{build_exact_div, LLVMBuildExactSDiv}
+    bin_op!{build_udiv, LLVMBuildUDiv}
+    bin_op!{build_rem, LLVMBuildSRem, LLVMBuildFRem}
+    bin_op!{build_urem, LLVMBuildURem}
     bin_op!{build_shl, LLVMBuildShl}
     bin_op!{build_ashr, LLVMBuildAShr}
+    bin_op!{build_lshr, LLVMBuildLShr}
     bin_op!{build_and, LLVMBuildAnd}
     bin_op!{build_or, LLVMBuildOr}
+    bin_op!{build_xor, LLVMBuildXor}
+    fn build_checked_op(&self, intrinsic: Intrinsic, a: &Value, b: &Value) -> (&Value, &Value) {
+        let func = self.get_intrinsic(intrinsic, &[a.get_type()]);
+        let pair = self.build_call(func, &[a, b]);
+        (self.build_extract_value(pair, 0), self.build_extract_value(pair, 1))
+    }
+    /// Build instructions that add `a` and `b` as signed or unsigned integers depending on `signed`.
+    ///
+    /// This will return the result and a boolean that is true if the addition overflowed.
+    pub fn build_checked_add(&self, a: &Value, b: &Value, signed: bool) -> (&Value, &Value) {
+        self.build_checked_op(if signed { Intrinsic::SAddWithOverflow } else { Intrinsic::UAddWithOverflow }, a, b)
+    }
+    /// Build instructions that subtract `b` from `a` as signed or unsigned integers depending on `signed`.
+    ///
+    /// This will return the result and a boolean that is true if the subtraction overflowed.
+    pub fn build_checked_sub(&self, a: &Value, b: &Value, signed: bool) -> (&Value, &Value) {
+        self.build_checked_op(if signed { Intrinsic::SSubWithOverflow } else { Intrinsic::USubWithOverflow }, a, b)
+    }
+    /// Build instructions that multiply `a` and `b` as signed or unsigned integers depending on `signed`.
+    ///
+    /// This will return the result and a boolean that is true if the multiplication overflowed.
+    pub fn build_checked_mul(&self, a: &Value, b: &Value, signed: bool) -> (&Value, &Value) {
+        self.build_checked_op(if signed { Intrinsic::SMulWithOverflow } else { Intrinsic::UMulWithOverflow }, a, b)
+    }
+    /// Build an instruction that branches to `trap` if `overflow` is true, then position the
+    /// builder at the end of a new block that runs otherwise and return it.
+    ///
+    /// This is meant to be used with the overflow flag returned by `build_checked_add` etc.
+    pub fn build_overflow_check(&self, overflow: &Value, trap: &BasicBlock) -> &BasicBlock {
+        let block = self.get_insert_block().expect("builder is not positioned in a block");
+        let func = block.get_parent().expect("block is not in a function");
+        let next = func.append("no_overflow");
+        next.move_after(block);
+        self.build_cond_br(overflow, trap, Some(next));
+        self.position_at_end(next);
+        next
+    }
     /// Build an instruction to compare the values `a` and `b` with the predicate / comparative operator `pred`.
     pub fn build_cmp(&self, a: &Value, b: &Value, pred: Predicate) -> &Value {
         let (at, bt) = (a.get_type(), b.get_type());
         assert_eq!(at, bt);
         if at.is_integer() {
-            let pred = match pred {
-                Predicate::Equal => LLVMIntPredicate::LLVMIntEQ,
-                Predicate::NotEqual => LLVMIntPredicate::LLVMIntNE,
-                Predicate::GreaterThan => LLVMIntPredicate::LLVMIntSGT,
-                Predicate::GreaterThanOrEqual => LLVMIntPredicate::LLVMIntSGE,
-                Predicate::LessThan => LLVMIntPredicate::LLVMIntSLT,
-                Predicate::LessThanOrEqual => LLVMIntPredicate::LLVMIntSLE
-            };
-            unsafe { core::LLVMBuildICmp(self.into(), pred, a.into(), b.into(), NULL_NAME.as_ptr()) }.into()
+            unsafe { core::LLVMBuildICmp(self.into(), pred.into(), a.into(), b.into(), NULL_NAME.as_ptr()) }.into()
         } else if at.is_float() {
-            let pred = match pred {
-                Predicate::Equal => LLVMRealPredicate::LLVMRealOEQ,
-                Predicate::NotEqual => LLVMRealPredicate::LLVMRealONE,
-                Predicate::GreaterThan => LLVMRealPredicate::LLVMRealOGT,
-                Predicate::GreaterThanOrEqual => LLVMRealPredicate::LLVMRealOGE,
-                Predicate::LessThan => LLVMRealPredicate::LLVMRealOLT,
-                Predicate::LessThanOrEqual => LLVMRealPredicate::LLVMRealOLE
-            };
-            unsafe { core::LLVMBuildFCmp(self.into(), pred, a.into(), b.into(), NULL_NAME.as_ptr()) }.into()
+            unsafe { core::LLVMBuildFCmp(self.into(), pred.into(), a.into(), b.into(), NULL_NAME.as_ptr()) }.into()
         } else {
-            panic!("expected numzextbers, got {:?}", at)
+            panic!("expected numbers, got {:?}", at)
+        }
+    }
+}
+/// Restores the position of a builder when it is dropped.
+///
+/// This is returned by `Builder::save_position`.
+pub struct PositionGuard<'a> {
+    builder: &'a Builder,
+    block: Option<&'a BasicBlock>,
+    next: Option<&'a Instruction>
+}
+impl<'a> PositionGuard<'a> {
+    /// Returns the block the builder will be positioned in once this is dropped.
+    pub fn get_block(&self) -> Option<&'a BasicBlock> {
+        self.block
+    }
+}
+impl<'a> Drop for PositionGuard<'a> {
+    fn drop(&mut self) {
+        match (self.block, self.next) {
+            (Some(block), Some(next)) => self.builder.position_at(block, next),
+            (Some(block), None) => self.builder.position_at_end(block),
+            (None, _) => self.builder.clear_insertion_position()
         }
     }
 }
\ No newline at end of file
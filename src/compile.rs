@@ -177,7 +177,7 @@ macro_rules! compile_func(
                 unsafe {
                     let as_usize: usize = mem::transmute(self);
                     let value = as_usize.compile(context);
-                    core::LLVMConstIntToPtr(value.into(), Self::get_type(context).into())
+                    core::LLVMConstIntToPtr(value.into(), PointerType::new(Self::get_type(context)).into())
                 }.into()
             }
             fn get_type(context: &'a Context) -> &'a Type {
@@ -189,7 +189,7 @@ macro_rules! compile_func(
                 unsafe {
                     let as_usize: usize = mem::transmute(self);
                     let value = as_usize.compile(context);
-                    core::LLVMConstIntToPtr(value.into(), Self::get_type(context).into())
+                    core::LLVMConstIntToPtr(value.into(), PointerType::new(Self::get_type(context)).into())
                 }.into()
             }
             fn get_type(context: &'a Context) -> &'a Type {
@@ -198,6 +198,22 @@ macro_rules! compile_func(
         }
     )
 );
+macro_rules! compile_variadic_func(
+    ($($name:ident),+) => (
+        impl<'a, R, $($name),+> Compile<'a> for unsafe extern fn($($name),+, ...) -> R where R:Compile<'a>, $($name:Compile<'a>),+ {
+            fn compile(self, context: &'a Context) -> &'a Value {
+                unsafe {
+                    let as_usize: usize = mem::transmute(self);
+                    let value = as_usize.compile(context);
+                    core::LLVMConstIntToPtr(value.into(), PointerType::new(Self::get_type(context)).into())
+                }.into()
+            }
+            fn get_type(context: &'a Context) -> &'a Type {
+                FunctionType::new_variadic(R::get_type(context), &[$($name::get_type(context)),+])
+            }
+        }
+    )
+);
 compile_func!{}
 compile_func!{A}
 compile_func!{A, B}
@@ -206,3 +222,10 @@ compile_func!{A, B, C, D}
 compile_func!{A, B, C, D, E}
 compile_func!{A, B, C, D, E, F}
 compile_func!{A, B, C, D, E, F, G}
+compile_variadic_func!{A}
+compile_variadic_func!{A, B}
+compile_variadic_func!{A, B, C}
+compile_variadic_func!{A, B, C, D}
+compile_variadic_func!{A, B, C, D, E}
+compile_variadic_func!{A, B, C, D, E, F}
+compile_variadic_func!{A, B, C, D, E, F, G}
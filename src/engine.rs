@@ -1,4 +1,4 @@
-use libc::{c_int, c_uint, c_ulonglong};
+use libc::{c_int, c_uint, c_ulonglong, c_void};
 use ffi::{core, target};
 use ffi::execution_engine as engine;
 use ffi::execution_engine::*;
@@ -70,6 +70,14 @@ pub trait ExecutionEngine<'a>:'a + Sized + DisposeRef where LLVMExecutionEngineR
     unsafe fn get_global<T>(&'a self, global: &'a Value) -> &'a T {
         mem::transmute(engine::LLVMGetPointerToGlobal(self.into(), global.into()))
     }
+    /// Map the global value given to the address given, so references to it will
+    /// use `addr` instead of looking its name up in the current process.
+    ///
+    /// This is marked as unsafe because the address cannot be guranteed to point to
+    /// something with the same type as the global value.
+    unsafe fn add_global_mapping<T>(&'a self, global: &'a Value, addr: *const T) {
+        engine::LLVMAddGlobalMapping(self.into(), global.into(), addr as *mut c_void)
+    }
     /// Returns a pointer to the global value with the name given.
     ///
     /// This is marked as unsafe because the type cannot be guranteed to be the same as the
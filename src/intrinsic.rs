@@ -0,0 +1,283 @@
+use ffi::{core, LLVMTypeKind};
+use context::Context;
+use types::{FunctionType, IntegerType, PointerType, StructType, Type, VectorType};
+use util::Sub;
+use value::Attribute;
+
+const NO_UNWIND: &'static [Attribute] = &[Attribute::NoUnwind];
+const NO_RETURN: &'static [Attribute] = &[Attribute::NoUnwind, Attribute::NoReturn];
+const READ_NONE: &'static [Attribute] = &[Attribute::NoUnwind, Attribute::ReadNone];
+
+const FLOAT: &'static [Overload] = &[Overload::Float];
+const INTEGER: &'static [Overload] = &[Overload::Integer];
+const MEMCPY: &'static [Overload] = &[Overload::Pointer, Overload::Pointer, Overload::Integer];
+const MEMSET: &'static [Overload] = &[Overload::Pointer, Overload::Integer];
+const NONE: &'static [Overload] = &[];
+
+/// The kind of type an intrinsic can be overloaded on.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Overload {
+    /// A float type or a vector of floats.
+    Float,
+    /// An integer type or a vector of integers.
+    Integer,
+    /// A pointer type in any address space.
+    Pointer
+}
+impl Overload {
+    /// Returns true if `ty` is this kind of type.
+    fn accepts(self, ty: &Type) -> bool {
+        let scalar = VectorType::from_super(ty).map(|vec| vec.get_element()).unwrap_or(ty);
+        match self {
+            Overload::Float => scalar.is_float(),
+            Overload::Integer => scalar.is_integer(),
+            Overload::Pointer => ty.is_pointer()
+        }
+    }
+}
+
+/// A function that LLVM provides, which is usually lowered to a single instruction
+/// or a call to a runtime library.
+///
+/// These are declared in a module with `Module::get_intrinsic`, which takes the types
+/// an intrinsic is overloaded on and works out its name and signature from them.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Intrinsic {
+    /// `T sqrt(T)`, overloaded on the float type `T`.
+    Sqrt,
+    /// `T sin(T)`, overloaded on the float type `T`.
+    Sin,
+    /// `T cos(T)`, overloaded on the float type `T`.
+    Cos,
+    /// `T exp(T)`, overloaded on the float type `T`.
+    Exp,
+    /// `T exp2(T)`, overloaded on the float type `T`.
+    Exp2,
+    /// `T log(T)`, overloaded on the float type `T`.
+    Log,
+    /// `T log2(T)`, overloaded on the float type `T`.
+    Log2,
+    /// `T log10(T)`, overloaded on the float type `T`.
+    Log10,
+    /// `T fabs(T)`, overloaded on the float type `T`.
+    Fabs,
+    /// `T floor(T)`, overloaded on the float type `T`.
+    Floor,
+    /// `T ceil(T)`, overloaded on the float type `T`.
+    Ceil,
+    /// `T trunc(T)`, overloaded on the float type `T`.
+    Trunc,
+    /// `T round(T)`, overloaded on the float type `T`.
+    Round,
+    /// `T pow(T, T)`, overloaded on the float type `T`.
+    Pow,
+    /// `T powi(T, i32)`, overloaded on the float type `T`.
+    Powi,
+    /// `T copysign(T, T)`, overloaded on the float type `T`.
+    CopySign,
+    /// `T minnum(T, T)`, overloaded on the float type `T`.
+    MinNum,
+    /// `T maxnum(T, T)`, overloaded on the float type `T`.
+    MaxNum,
+    /// `T fma(T, T, T)`, overloaded on the float type `T`.
+    Fma,
+    /// `T fmuladd(T, T, T)`, overloaded on the float type `T`.
+    FMulAdd,
+    /// `T ctpop(T)`, overloaded on the integer type `T`.
+    Ctpop,
+    /// `T ctlz(T, i1 is_zero_undef)`, overloaded on the integer type `T`.
+    Ctlz,
+    /// `T cttz(T, i1 is_zero_undef)`, overloaded on the integer type `T`.
+    Cttz,
+    /// `T bswap(T)`, overloaded on the integer type `T`.
+    Bswap,
+    /// `{T, i1} sadd.with.overflow(T, T)`, overloaded on the integer type `T`.
+    SAddWithOverflow,
+    /// `{T, i1} uadd.with.overflow(T, T)`, overloaded on the integer type `T`.
+    UAddWithOverflow,
+    /// `{T, i1} ssub.with.overflow(T, T)`, overloaded on the integer type `T`.
+    SSubWithOverflow,
+    /// `{T, i1} usub.with.overflow(T, T)`, overloaded on the integer type `T`.
+    USubWithOverflow,
+    /// `{T, i1} smul.with.overflow(T, T)`, overloaded on the integer type `T`.
+    SMulWithOverflow,
+    /// `{T, i1} umul.with.overflow(T, T)`, overloaded on the integer type `T`.
+    UMulWithOverflow,
+    /// `void memcpy(D, S, L, i32 align, i1 volatile)`, overloaded on the pointer types `D`
+    /// and `S` and the integer type `L`.
+    Memcpy,
+    /// `void memmove(D, S, L, i32 align, i1 volatile)`, overloaded on the pointer types `D`
+    /// and `S` and the integer type `L`.
+    Memmove,
+    /// `void memset(D, i8, L, i32 align, i1 volatile)`, overloaded on the pointer type `D`
+    /// and the integer type `L`.
+    Memset,
+    /// `void lifetime.start(i64 size, i8*)`, which isn't overloaded.
+    LifetimeStart,
+    /// `void lifetime.end(i64 size, i8*)`, which isn't overloaded.
+    LifetimeEnd,
+    /// `void trap()`, which isn't overloaded.
+    Trap,
+    /// `void va_start(i8* list)`, which isn't overloaded.
+    VaStart,
+    /// `void va_end(i8* list)`, which isn't overloaded.
+    VaEnd,
+    /// `void va_copy(i8* dest, i8* src)`, which isn't overloaded.
+    VaCopy
+}
+impl Intrinsic {
+    /// Returns the name of this intrinsic without any overloaded types mangled into it.
+    pub fn get_base_name(self) -> &'static str {
+        match self {
+            Intrinsic::Sqrt => "llvm.sqrt",
+            Intrinsic::Sin => "llvm.sin",
+            Intrinsic::Cos => "llvm.cos",
+            Intrinsic::Exp => "llvm.exp",
+            Intrinsic::Exp2 => "llvm.exp2",
+            Intrinsic::Log => "llvm.log",
+            Intrinsic::Log2 => "llvm.log2",
+            Intrinsic::Log10 => "llvm.log10",
+            Intrinsic::Fabs => "llvm.fabs",
+            Intrinsic::Floor => "llvm.floor",
+            Intrinsic::Ceil => "llvm.ceil",
+            Intrinsic::Trunc => "llvm.trunc",
+            Intrinsic::Round => "llvm.round",
+            Intrinsic::Pow => "llvm.pow",
+            Intrinsic::Powi => "llvm.powi",
+            Intrinsic::CopySign => "llvm.copysign",
+            Intrinsic::MinNum => "llvm.minnum",
+            Intrinsic::MaxNum => "llvm.maxnum",
+            Intrinsic::Fma => "llvm.fma",
+            Intrinsic::FMulAdd => "llvm.fmuladd",
+            Intrinsic::Ctpop => "llvm.ctpop",
+            Intrinsic::Ctlz => "llvm.ctlz",
+            Intrinsic::Cttz => "llvm.cttz",
+            Intrinsic::Bswap => "llvm.bswap",
+            Intrinsic::SAddWithOverflow => "llvm.sadd.with.overflow",
+            Intrinsic::UAddWithOverflow => "llvm.uadd.with.overflow",
+            Intrinsic::SSubWithOverflow => "llvm.ssub.with.overflow",
+            Intrinsic::USubWithOverflow => "llvm.usub.with.overflow",
+            Intrinsic::SMulWithOverflow => "llvm.smul.with.overflow",
+            Intrinsic::UMulWithOverflow => "llvm.umul.with.overflow",
+            Intrinsic::Memcpy => "llvm.memcpy",
+            Intrinsic::Memmove => "llvm.memmove",
+            Intrinsic::Memset => "llvm.memset",
+            Intrinsic::LifetimeStart => "llvm.lifetime.start",
+            Intrinsic::LifetimeEnd => "llvm.lifetime.end",
+            Intrinsic::Trap => "llvm.trap",
+            Intrinsic::VaStart => "llvm.va_start",
+            Intrinsic::VaEnd => "llvm.va_end",
+            Intrinsic::VaCopy => "llvm.va_copy"
+        }
+    }
+    /// Returns the number of types this intrinsic is overloaded on.
+    pub fn num_overloads(self) -> usize {
+        self.get_overloads().len()
+    }
+    /// Returns the name of this intrinsic with the overloaded types `types` mangled into it.
+    pub fn get_name(self, types: &[&Type]) -> String {
+        self.check_overloads(types);
+        let mut name = self.get_base_name().to_string();
+        for ty in types {
+            name.push('.');
+            name.push_str(&mangle(ty));
+        }
+        name
+    }
+    /// Returns the signature of this intrinsic with the overloaded types `types`.
+    pub fn get_signature<'a>(self, context: &'a Context, types: &[&'a Type]) -> &'a FunctionType {
+        self.check_overloads(types);
+        let void_t = Type::get::<()>(context);
+        let bool_t = Type::get::<bool>(context);
+        let i32_t = Type::get::<i32>(context);
+        match self {
+            Intrinsic::Sqrt | Intrinsic::Sin | Intrinsic::Cos | Intrinsic::Exp | Intrinsic::Exp2 |
+            Intrinsic::Log | Intrinsic::Log2 | Intrinsic::Log10 | Intrinsic::Fabs | Intrinsic::Floor |
+            Intrinsic::Ceil | Intrinsic::Trunc | Intrinsic::Round | Intrinsic::Ctpop | Intrinsic::Bswap =>
+                FunctionType::new(types[0], &[types[0]]),
+            Intrinsic::Pow | Intrinsic::CopySign | Intrinsic::MinNum | Intrinsic::MaxNum =>
+                FunctionType::new(types[0], &[types[0], types[0]]),
+            Intrinsic::Powi =>
+                FunctionType::new(types[0], &[types[0], i32_t]),
+            Intrinsic::Fma | Intrinsic::FMulAdd =>
+                FunctionType::new(types[0], &[types[0], types[0], types[0]]),
+            Intrinsic::Ctlz | Intrinsic::Cttz =>
+                FunctionType::new(types[0], &[types[0], bool_t]),
+            Intrinsic::SAddWithOverflow | Intrinsic::UAddWithOverflow | Intrinsic::SSubWithOverflow |
+            Intrinsic::USubWithOverflow | Intrinsic::SMulWithOverflow | Intrinsic::UMulWithOverflow => {
+                let ret = StructType::new(context, &[types[0], bool_t], false);
+                FunctionType::new(ret, &[types[0], types[0]])
+            },
+            Intrinsic::Memcpy | Intrinsic::Memmove =>
+                FunctionType::new(void_t, &[types[0], types[1], types[2], i32_t, bool_t]),
+            Intrinsic::Memset =>
+                FunctionType::new(void_t, &[types[0], Type::get::<u8>(context), types[1], i32_t, bool_t]),
+            Intrinsic::LifetimeStart | Intrinsic::LifetimeEnd =>
+                FunctionType::new(void_t, &[Type::get::<u64>(context), PointerType::new(Type::get::<u8>(context))]),
+            Intrinsic::Trap =>
+                FunctionType::new(void_t, &[]),
+            Intrinsic::VaStart | Intrinsic::VaEnd =>
+                FunctionType::new(void_t, &[PointerType::new(Type::get::<u8>(context))]),
+            Intrinsic::VaCopy => {
+                let byte_ptr = PointerType::new(Type::get::<u8>(context));
+                FunctionType::new(void_t, &[byte_ptr, byte_ptr])
+            }
+        }
+    }
+    /// Returns the attributes LLVM gives this intrinsic.
+    pub fn get_attributes(self) -> &'static [Attribute] {
+        match self {
+            Intrinsic::Memcpy | Intrinsic::Memmove | Intrinsic::Memset |
+            Intrinsic::LifetimeStart | Intrinsic::LifetimeEnd |
+            Intrinsic::VaStart | Intrinsic::VaEnd | Intrinsic::VaCopy => NO_UNWIND,
+            Intrinsic::Trap => NO_RETURN,
+            _ => READ_NONE
+        }
+    }
+    /// Returns the kinds of the types this intrinsic is overloaded on.
+    fn get_overloads(self) -> &'static [Overload] {
+        match self {
+            Intrinsic::Ctpop | Intrinsic::Ctlz | Intrinsic::Cttz | Intrinsic::Bswap |
+            Intrinsic::SAddWithOverflow | Intrinsic::UAddWithOverflow | Intrinsic::SSubWithOverflow |
+            Intrinsic::USubWithOverflow | Intrinsic::SMulWithOverflow | Intrinsic::UMulWithOverflow => INTEGER,
+            Intrinsic::Memcpy | Intrinsic::Memmove => MEMCPY,
+            Intrinsic::Memset => MEMSET,
+            Intrinsic::LifetimeStart | Intrinsic::LifetimeEnd | Intrinsic::Trap |
+            Intrinsic::VaStart | Intrinsic::VaEnd | Intrinsic::VaCopy => NONE,
+            _ => FLOAT
+        }
+    }
+    fn check_overloads(self, types: &[&Type]) {
+        let overloads = self.get_overloads();
+        if types.len() != overloads.len() {
+            panic!("{:?} is overloaded on {} types, got {}", self, overloads.len(), types.len())
+        }
+        for (&overload, ty) in overloads.iter().zip(types) {
+            if !overload.accepts(ty) {
+                panic!("{:?} is overloaded on {:?} types, got {:?}", self, overload, ty)
+            }
+        }
+    }
+}
+
+/// Returns the type given as it is mangled into the name of an intrinsic.
+fn mangle(ty: &Type) -> String {
+    if let Some(ty) = IntegerType::from_super(ty) {
+        format!("i{}", ty.get_width())
+    } else if let Some(ty) = VectorType::from_super(ty) {
+        format!("v{}{}", ty.get_size(), mangle(ty.get_element()))
+    } else if let Some(ptr) = PointerType::from_super(ty) {
+        let space = unsafe { core::LLVMGetPointerAddressSpace(ty.into()) };
+        format!("p{}{}", space, mangle(ptr.get_element()))
+    } else {
+        match unsafe { core::LLVMGetTypeKind(ty.into()) } {
+            LLVMTypeKind::LLVMHalfTypeKind => "f16".into(),
+            LLVMTypeKind::LLVMFloatTypeKind => "f32".into(),
+            LLVMTypeKind::LLVMDoubleTypeKind => "f64".into(),
+            LLVMTypeKind::LLVMX86_FP80TypeKind => "f80".into(),
+            LLVMTypeKind::LLVMFP128TypeKind => "f128".into(),
+            LLVMTypeKind::LLVMPPC_FP128TypeKind => "ppcf128".into(),
+            _ => panic!("can't mangle {:?} into an intrinsic name", ty)
+        }
+    }
+}
@@ -16,6 +16,7 @@ mod builder;
 mod compile;
 mod context;
 mod engine;
+mod intrinsic;
 mod module;
 mod object;
 mod target;
@@ -24,14 +25,15 @@ pub mod value;
 mod util;
 
 pub use cbox::{CBox, CSemiBox};
-pub use builder::Builder;
+pub use builder::{Builder, PositionGuard};
 pub use block::BasicBlock;
 pub use compile::Compile;
 pub use context::{Context, GetContext};
+pub use intrinsic::Intrinsic;
 pub use engine::{JitEngine, JitOptions, Interpreter, ExecutionEngine, GenericValue, GenericValueCast};
-pub use module::{AddressSpace, Module, Functions};
+pub use module::{AddressSpace, Module, Functions, Globals};
 pub use object::{ObjectFile, Symbol, Symbols};
 pub use target::{TargetData, Target};
 pub use types::*;
-pub use value::{Alias, Arg, Attribute, Value, Function, GlobalValue, GlobalVariable, Linkage, Predicate};
+pub use value::{Alias, AllocaInst, Arg, AtomicBinOp, AtomicOrdering, Attribute, BranchInst, CallConv, CallInst, DllStorageClass, Value, Function, GlobalValue, GlobalVariable, IndirectBr, InlineAsm, Instruction, LandingPad, Linkage, LoadInst, Opcode, Phi, Predicate, ReturnInst, StoreInst, Switch, ThreadLocalMode, Use, Visibility};
 pub use util::Sub;
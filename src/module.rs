@@ -7,7 +7,7 @@ use ffi::bit_writer as writer;
 use ffi::bit_reader as reader;
 use cbox::{CBox, CSemiBox};
 use std::ffi::CString;
-use std::iter::{Iterator, IntoIterator};
+use std::iter::{Iterator, DoubleEndedIterator, IntoIterator};
 use std::io::{Error, ErrorKind};
 use std::io::Result as IoResult;
 use std::{env, fmt, mem};
@@ -16,6 +16,7 @@ use std::path::Path;
 use std::process::Command;
 use buffer::MemoryBuffer;
 use context::{Context, GetContext};
+use intrinsic::Intrinsic;
 use value::{Alias, Function, GlobalValue, GlobalVariable, Value};
 use types::Type;
 use util;
@@ -66,6 +67,15 @@ impl Module {
             core::LLVMAddAlias(self.into(), val.get_type().into(), val.into(), ptr).into()
         })
     }
+    /// Iterate through the global variables in the module.
+    pub fn globals(&self) -> Globals {
+        unsafe {
+            Globals {
+                min: util::ptr_to_null(core::LLVMGetFirstGlobal(self.into())),
+                max: util::ptr_to_null(core::LLVMGetLastGlobal(self.into()))
+            }
+        }
+    }
     /// Get the global with the name given, or `None` if no global with that name exists.
     pub fn get_global<'a>(&'a self, name: &str) -> Option<&'a GlobalValue> {
         util::with_cstr(name, |ptr| unsafe {
@@ -101,6 +111,26 @@ impl Module {
         let c_name = CString::new(name).unwrap();
         unsafe { core::LLVMAddFunction(self.into(), c_name.as_ptr(), sig.into()) }.into()
     }
+    /// Returns the declaration of the intrinsic `intrinsic` overloaded on the types `types`,
+    /// adding it to the module if it hasn't been already.
+    ///
+    /// ```rust
+    /// use llvm::*;
+    /// let context = Context::new();
+    /// let module = Module::new("intrinsics", &context);
+    /// let sqrt = module.get_intrinsic(Intrinsic::Sqrt, &[Type::get::<f64>(&context)]);
+    /// assert_eq!(sqrt.get_name(), Some("llvm.sqrt.f64"));
+    /// ```
+    pub fn get_intrinsic<'a>(&'a self, intrinsic: Intrinsic, types: &[&'a Type]) -> &'a Function {
+        let name = intrinsic.get_name(types);
+        if let Some(func) = self.get_function(&name) {
+            return func;
+        }
+        let sig = intrinsic.get_signature(self.get_context(), types);
+        let func = self.add_function(&name, sig);
+        func.add_attributes(intrinsic.get_attributes());
+        func
+    }
     /// Returns the function with the name given, or `None` if no function with that name exists.
     pub fn get_function<'a>(&'a self, name: &str) -> Option<&'a Function> {
         let c_name = CString::new(name).unwrap();
@@ -242,10 +272,40 @@ impl<'a> Iterator for Functions<'a> {
         if self.value.is_null() {
             None
         } else {
-            let c_next = unsafe { core::LLVMGetNextFunction(self.value) };
-            self.value = c_next;
-            Some(self.value.into())
+            let c_func = self.value;
+            self.value = unsafe { core::LLVMGetNextFunction(c_func) };
+            Some(c_func.into())
+        }
+    }
+}
+/// An iterator through the global variables contained in a module.
+pub struct Globals<'a> {
+    min: Option<&'a GlobalVariable>,
+    max: Option<&'a GlobalVariable>
+}
+impl<'a> Iterator for Globals<'a> {
+    type Item = &'a GlobalVariable;
+    fn next(&mut self) -> Option<&'a GlobalVariable> {
+        let global = self.min;
+        if self.min == self.max {
+            self.min = None;
+            self.max = None;
+        } else if let Some(min) = self.min {
+            self.min = unsafe { util::ptr_to_null(core::LLVMGetNextGlobal(min.into())) };
         }
+        global
+    }
+}
+impl<'a> DoubleEndedIterator for Globals<'a> {
+    fn next_back(&mut self) -> Option<&'a GlobalVariable> {
+        let global = self.max;
+        if self.min == self.max {
+            self.min = None;
+            self.max = None;
+        } else if let Some(max) = self.max {
+            self.max = unsafe { util::ptr_to_null(core::LLVMGetPreviousGlobal(max.into())) };
+        }
+        global
     }
 }
 
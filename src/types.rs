@@ -55,6 +55,13 @@ impl Type {
         let kind = unsafe { core::LLVMGetTypeKind(self.into()) };
         kind as c_uint == LLVMTypeKind::LLVMStructTypeKind as c_uint
     }
+    /// Returns true if this type is an array.
+    ///
+    /// This is equivalent to `ArrayType::is`.
+    pub fn is_array(&self) -> bool {
+        let kind = unsafe { core::LLVMGetTypeKind(self.into()) };
+        kind as c_uint == LLVMTypeKind::LLVMArrayTypeKind as c_uint
+    }
     /// Returns true if this type is void.
     pub fn is_void(&self) -> bool {
         let kind = unsafe { core::LLVMGetTypeKind(self.into()) };
@@ -137,6 +144,15 @@ impl FunctionType {
     pub fn new<'a>(ret: &'a Type, args: &[&'a Type]) -> &'a FunctionType {
         unsafe { core::LLVMFunctionType(ret.into(), args.as_ptr() as *mut LLVMTypeRef, args.len() as c_uint, 0) }.into()
     }
+    /// Make a new function signature with the return type and arguments given, which can
+    /// also take a variable number of arguments after them, like C's `printf`.
+    pub fn new_variadic<'a>(ret: &'a Type, args: &[&'a Type]) -> &'a FunctionType {
+        unsafe { core::LLVMFunctionType(ret.into(), args.as_ptr() as *mut LLVMTypeRef, args.len() as c_uint, 1) }.into()
+    }
+    /// Returns true if this signature takes a variable number of arguments.
+    pub fn is_variadic(&self) -> bool {
+        unsafe { core::LLVMIsFunctionVarArg(self.into()) != 0 }
+    }
     /// Returns the number of parameters this signature takes.
     pub fn num_params(&self) -> usize {
         unsafe { core::LLVMCountParamTypes(self.into()) as usize }
@@ -178,7 +194,7 @@ pub struct IntegerType(PhantomData<[u8]>);
 native_ref!{&IntegerType = LLVMTypeRef}
 get_context!{IntegerType, LLVMGetTypeContext}
 to_str!{IntegerType, LLVMPrintTypeToString}
-sub!{IntegerType, LLVMPointerTypeKind}
+sub!{IntegerType, LLVMIntegerTypeKind}
 impl IntegerType {
     /// Make a new integer type that will be the size of the given number of bits.
     pub fn new(context: &Context, numbits: usize) -> &IntegerType {
@@ -1,12 +1,13 @@
-use libc::{c_char, c_uint, c_int};
-use ffi::prelude::LLVMValueRef;
+use libc::{c_char, c_uint, c_int, c_ulonglong};
+use ffi::prelude::{LLVMBasicBlockRef, LLVMUseRef, LLVMValueRef};
 use ffi::{core, LLVMAttribute};
-use ffi::LLVMLinkage;
+use ffi::{LLVMAtomicOrdering, LLVMAtomicRMWBinOp, LLVMDLLStorageClass, LLVMIntPredicate, LLVMLinkage};
+use ffi::{LLVMOpcode, LLVMRealPredicate, LLVMThreadLocalMode, LLVMVisibility};
 use std::ffi::CString;
 use std::{fmt, mem};
 use std::ops::{Deref, Index};
 use std::marker::PhantomData;
-use block::{BasicBlock, BlockIter};
+use block::{BasicBlock, BlockIter, FunctionInstrIter};
 use context::{Context, GetContext};
 use types::{FunctionType, Type};
 use util::{self, Sub};
@@ -31,10 +32,45 @@ impl Deref for $this {
     fn deref(&self) -> &$sup {
         self.to_super()
     }
+}
+impl $this {
+    /// Set the name of this value, then return it.
+    pub fn named(&self, name: &str) -> &$this {
+        self.set_name(name);
+        self
+    }
 }
     )
 }
 
+macro_rules! const_op(
+    ($name:ident, $func:ident) => (
+        pub fn $name<'a>(left: &'a Value, right: &'a Value) -> &'a Value {
+            unsafe { core::$func(left.into(), right.into()) }.into()
+        }
+    );
+    ($name:ident, $ifunc:ident, $ffunc:ident) => (
+        pub fn $name<'a>(left: &'a Value, right: &'a Value) -> &'a Value {
+            let ty = left.get_type();
+            unsafe {
+                (if ty.is_integer() {
+                    core::$ifunc
+                } else {
+                    core::$ffunc
+                })(left.into(), right.into()).into()
+            }
+        }
+    );
+);
+macro_rules! const_cast_op(
+    ($(#[$attr:meta])* $name:ident, $func:ident) => (
+        $(#[$attr])*
+        pub fn $name<'a>(value: &'a Value, dest: &'a Type) -> &'a Value {
+            unsafe { core::$func(value.into(), dest.into()) }.into()
+        }
+    );
+);
+
 /// A typed value that can be used as an operand in instructions.
 pub struct Value(PhantomData<[u8]>);
 native_ref!(&Value = LLVMValueRef);
@@ -48,6 +84,20 @@ impl Value {
     pub fn new_vector<'a>(vals: &[&'a Value]) -> &'a Value {
         unsafe { core::LLVMConstVector(vals.as_ptr() as *mut LLVMValueRef, vals.len() as c_uint).into() }
     }
+    /// Create a new constant vector that repeats `val` `length` times.
+    pub fn new_splat<'a>(val: &'a Value, length: usize) -> &'a Value {
+        let vals = vec![val; length];
+        Value::new_vector(&vals)
+    }
+    /// Create a new constant shuffle mask from the element indices given, for use with
+    /// `Builder::build_shuffle_vector`.
+    pub fn new_shuffle_mask<'a>(context: &'a Context, indices: &[usize]) -> &'a Value {
+        unsafe {
+            let i32_t = core::LLVMInt32TypeInContext(context.into());
+            let vals:Vec<LLVMValueRef> = indices.iter().map(|&index| core::LLVMConstInt(i32_t, index as c_ulonglong, 0)).collect();
+            core::LLVMConstVector(vals.as_ptr() as *mut LLVMValueRef, vals.len() as c_uint).into()
+        }
+    }
     /// Create a new constant C string from the text given.
     pub fn new_string<'a>(context: &'a Context, text: &str, rust_style: bool) -> &'a Value {
         unsafe {
@@ -56,10 +106,197 @@ impl Value {
             core::LLVMConstStringInContext(context.into(), ptr, len, rust_style as c_int).into()
         }
     }
+    /// Create a new constant address of the block `block` in the function `func`, which
+    /// can be branched to with `Builder::build_indirect_br`.
+    pub fn new_block_address<'a>(func: &'a Function, block: &'a BasicBlock) -> &'a Value {
+        unsafe { core::LLVMBlockAddress(func.into(), block.into()).into() }
+    }
     /// Create a new constant undefined value of the given type.
     pub fn new_undef<'a>(ty: &'a Type) -> &'a Value {
         unsafe { core::LLVMGetUndef(ty.into()).into() }
     }
+    /// Create a new constant zero value of the given type.
+    ///
+    /// This is `zeroinitializer` for aggregate types and `null` for pointers.
+    pub fn new_null<'a>(ty: &'a Type) -> &'a Value {
+        unsafe { core::LLVMConstNull(ty.into()).into() }
+    }
+    /// Create a new constant null pointer of the pointer type given.
+    pub fn new_null_pointer<'a>(ty: &'a Type) -> &'a Value {
+        assert!(ty.is_pointer(), "expected a pointer type, got {:?}", ty);
+        unsafe { core::LLVMConstPointerNull(ty.into()).into() }
+    }
+    /// Create a new constant integer or integer vector of the given type with every bit set.
+    pub fn new_all_ones<'a>(ty: &'a Type) -> &'a Value {
+        unsafe { core::LLVMConstAllOnes(ty.into()).into() }
+    }
+    /// Create a new constant array of the values given, which must all have the type `elem`.
+    pub fn new_array<'a>(elem: &'a Type, vals: &[&'a Value]) -> &'a Value {
+        for val in vals {
+            assert!(val.get_type() == elem, "expected array element of type {:?}, got {:?}", elem, val.get_type());
+        }
+        unsafe { core::LLVMConstArray(elem.into(), vals.as_ptr() as *mut LLVMValueRef, vals.len() as c_uint).into() }
+    }
+    /// Create a new constant pointer to an element of the constant pointer `ptr`, using the
+    /// constant indices given.
+    pub fn const_gep<'a>(ptr: &'a Value, indices: &[&'a Value]) -> &'a Value {
+        unsafe { core::LLVMConstGEP(ptr.into(), indices.as_ptr() as *mut LLVMValueRef, indices.len() as c_uint).into() }
+    }
+    /// Create a new constant pointer to an element of the constant pointer `ptr`, using the
+    /// constant indices given, which must stay within the bounds of the object it points to.
+    pub fn const_in_bounds_gep<'a>(ptr: &'a Value, indices: &[&'a Value]) -> &'a Value {
+        unsafe { core::LLVMConstInBoundsGEP(ptr.into(), indices.as_ptr() as *mut LLVMValueRef, indices.len() as c_uint).into() }
+    }
+    const_op!{const_add, LLVMConstAdd, LLVMConstFAdd}
+    const_op!{const_sub, LLVMConstSub, LLVMConstFSub}
+    const_op!{const_mul, LLVMConstMul, LLVMConstFMul}
+    const_op!{const_div, LLVMConstSDiv, LLVMConstFDiv}
+    const_op!{const_udiv, LLVMConstUDiv}
+    const_op!{const_rem, LLVMConstSRem, LLVMConstFRem}
+    const_op!{const_urem, LLVMConstURem}
+    const_op!{const_shl, LLVMConstShl}
+    const_op!{const_ashr, LLVMConstAShr}
+    const_op!{const_lshr, LLVMConstLShr}
+    const_op!{const_and, LLVMConstAnd}
+    const_op!{const_or, LLVMConstOr}
+    const_op!{const_xor, LLVMConstXor}
+    /// Create a new constant that negates the constant `value`.
+    pub fn const_neg<'a>(value: &'a Value) -> &'a Value {
+        unsafe {
+            if value.get_type().is_integer() {
+                core::LLVMConstNeg(value.into())
+            } else {
+                core::LLVMConstFNeg(value.into())
+            }.into()
+        }
+    }
+    /// Create a new constant that inverts the bits of the constant integer `value`.
+    pub fn const_not<'a>(value: &'a Value) -> &'a Value {
+        unsafe { core::LLVMConstNot(value.into()).into() }
+    }
+    /// Create a new constant that compares the constants `a` and `b` with the predicate given.
+    pub fn const_cmp<'a>(a: &'a Value, b: &'a Value, pred: Predicate) -> &'a Value {
+        let (at, bt) = (a.get_type(), b.get_type());
+        assert_eq!(at, bt);
+        if at.is_integer() {
+            unsafe { core::LLVMConstICmp(pred.into(), a.into(), b.into()) }.into()
+        } else if at.is_float() {
+            unsafe { core::LLVMConstFCmp(pred.into(), a.into(), b.into()) }.into()
+        } else {
+            panic!("expected numbers, got {:?}", at)
+        }
+    }
+    const_cast_op!{
+        /// Create a new constant that truncates the constant integer `value` to the smaller
+        /// integer type `dest`.
+        const_trunc, LLVMConstTrunc
+    }
+    const_cast_op!{
+        /// Create a new constant that zero-extends the constant integer `value` to the larger
+        /// integer type `dest`.
+        const_zext, LLVMConstZExt
+    }
+    const_cast_op!{
+        /// Create a new constant that sign-extends the constant integer `value` to the larger
+        /// integer type `dest`.
+        const_sext, LLVMConstSExt
+    }
+    const_cast_op!{
+        /// Create a new constant that truncates the constant float `value` to the smaller
+        /// float type `dest`.
+        const_fp_trunc, LLVMConstFPTrunc
+    }
+    const_cast_op!{
+        /// Create a new constant that extends the constant float `value` to the larger float
+        /// type `dest`.
+        const_fp_ext, LLVMConstFPExt
+    }
+    const_cast_op!{
+        /// Create a new constant that converts the constant float `value` to the unsigned
+        /// integer type `dest`.
+        const_fp_to_ui, LLVMConstFPToUI
+    }
+    const_cast_op!{
+        /// Create a new constant that converts the constant float `value` to the signed
+        /// integer type `dest`.
+        const_fp_to_si, LLVMConstFPToSI
+    }
+    const_cast_op!{
+        /// Create a new constant that converts the constant unsigned integer `value` to the
+        /// float type `dest`.
+        const_ui_to_fp, LLVMConstUIToFP
+    }
+    const_cast_op!{
+        /// Create a new constant that converts the constant signed integer `value` to the
+        /// float type `dest`.
+        const_si_to_fp, LLVMConstSIToFP
+    }
+    const_cast_op!{
+        /// Create a new constant that converts the constant pointer `value` to the integer
+        /// type `dest`.
+        const_ptr_to_int, LLVMConstPtrToInt
+    }
+    const_cast_op!{
+        /// Create a new constant that converts the constant integer `value` to the pointer
+        /// type `dest`.
+        const_int_to_ptr, LLVMConstIntToPtr
+    }
+    const_cast_op!{
+        /// Create a new constant that reinterprets the bits of the constant `value` as the
+        /// type `dest`, which must be the same size.
+        const_bit_cast, LLVMConstBitCast
+    }
+    const_cast_op!{
+        /// Create a new constant that converts the constant pointer `value` to the pointer
+        /// type `dest` in another address space.
+        const_address_space_cast, LLVMConstAddrSpaceCast
+    }
+    /// Returns true if this value is a constant.
+    pub fn is_constant(&self) -> bool {
+        unsafe { core::LLVMIsConstant(self.into()) != 0 }
+    }
+    /// Returns true if this value is a constant zero, null pointer or `zeroinitializer`.
+    pub fn is_null(&self) -> bool {
+        unsafe { core::LLVMIsNull(self.into()) != 0 }
+    }
+    /// Returns true if this value is a constant undefined value.
+    pub fn is_undef(&self) -> bool {
+        unsafe { core::LLVMIsUndef(self.into()) != 0 }
+    }
+    /// Returns the value of this constant integer zero-extended to 64 bits, or `None` if
+    /// this isn't a constant integer.
+    pub fn get_zext_value(&self) -> Option<u64> {
+        unsafe {
+            if core::LLVMIsAConstantInt(self.into()).is_null() {
+                None
+            } else {
+                Some(core::LLVMConstIntGetZExtValue(self.into()) as u64)
+            }
+        }
+    }
+    /// Returns the value of this constant integer sign-extended to 64 bits, or `None` if
+    /// this isn't a constant integer.
+    pub fn get_sext_value(&self) -> Option<i64> {
+        unsafe {
+            if core::LLVMIsAConstantInt(self.into()).is_null() {
+                None
+            } else {
+                Some(core::LLVMConstIntGetSExtValue(self.into()) as i64)
+            }
+        }
+    }
+    /// Returns the value of this constant float as a double, or `None` if this isn't a
+    /// constant float.
+    pub fn get_double_value(&self) -> Option<f64> {
+        unsafe {
+            if core::LLVMIsAConstantFP(self.into()).is_null() {
+                None
+            } else {
+                let mut loses_info = 0;
+                Some(core::LLVMConstRealGetDouble(self.into(), &mut loses_info) as f64)
+            }
+        }
+    }
     /// Returns the name of this value, or `None` if it lacks a name
     pub fn get_name(&self) -> Option<&str> {
         unsafe {
@@ -74,20 +311,330 @@ impl Value {
             core::LLVMSetValueName(self.into(), c_name.as_ptr())
         }
     }
+    /// Set the name of this value, then return it.
+    ///
+    /// This is useful for naming the results of `Builder` methods so the printed
+    /// IR is easier to read, such as `builder.build_add(a, b).named("sum")`.
+    pub fn named(&self, name: &str) -> &Value {
+        self.set_name(name);
+        self
+    }
     /// Returns the type of this value
     pub fn get_type(&self) -> &Type {
         unsafe { core::LLVMTypeOf(self.into()) }.into()
     }
+    /// Set whether this memory access is volatile.
+    ///
+    /// This must be a load, store, `atomicrmw` or `cmpxchg` instruction.
+    pub fn set_volatile(&self, is_volatile: bool) {
+        unsafe { core::LLVMSetVolatile(self.into(), is_volatile as c_int) }
+    }
+    /// Returns true if this memory access is volatile.
+    ///
+    /// This must be a load, store, `atomicrmw` or `cmpxchg` instruction.
+    pub fn is_volatile(&self) -> bool {
+        unsafe { core::LLVMGetVolatile(self.into()) != 0 }
+    }
+    /// Set the alignment of this value in bytes.
+    ///
+    /// This must be a load, store or `alloca` instruction or a global value.
+    pub fn set_alignment(&self, align: usize) {
+        unsafe { core::LLVMSetAlignment(self.into(), align as c_uint) }
+    }
+    /// Returns the alignment of this value in bytes.
+    ///
+    /// This must be a load, store or `alloca` instruction or a global value.
+    pub fn get_alignment(&self) -> usize {
+        unsafe { core::LLVMGetAlignment(self.into()) as usize }
+    }
+    /// Set the atomic ordering of this memory access.
+    ///
+    /// This must be a load or store instruction.
+    pub fn set_ordering(&self, ordering: AtomicOrdering) {
+        unsafe { core::LLVMSetOrdering(self.into(), ordering.into()) }
+    }
+    /// Returns the atomic ordering of this memory access.
+    ///
+    /// This must be a load or store instruction.
+    pub fn get_ordering(&self) -> AtomicOrdering {
+        unsafe { core::LLVMGetOrdering(self.into()).into() }
+    }
+    /// Iterate through the uses of this value.
+    pub fn uses(&self) -> UseIter {
+        UseIter {
+            current: unsafe { util::ptr_to_null(core::LLVMGetFirstUse(self.into())) }
+        }
+    }
+    /// Returns the number of times this value is used.
+    pub fn num_uses(&self) -> usize {
+        self.uses().count()
+    }
+    /// Returns true if this value is used exactly once.
+    pub fn has_one_use(&self) -> bool {
+        let mut uses = self.uses();
+        uses.next().is_some() && uses.next().is_none()
+    }
+    /// Replace every use of this value with `other`, which must have the same type.
+    pub fn replace_all_uses_with(&self, other: &Value) {
+        assert!(self.get_type() == other.get_type(), "replacement value has type {:?}, expected {:?}", other.get_type(), self.get_type());
+        unsafe { core::LLVMReplaceAllUsesWith(self.into(), other.into()) }
+    }
+}
+/// Fail with the number of uses `value` has if it is still used.
+fn check_unused(value: &Value) -> Result<(), usize> {
+    match value.num_uses() {
+        0 => Ok(()),
+        uses => Err(uses)
+    }
+}
+/// A single use of a value as an operand of another value, its user.
+pub struct Use(PhantomData<[u8]>);
+native_ref!(&Use = LLVMUseRef);
+impl Use {
+    /// Returns the value that uses the used value, such as an instruction.
+    pub fn get_user(&self) -> &Value {
+        unsafe { core::LLVMGetUser(self.into()).into() }
+    }
+    /// Returns the value that is being used.
+    pub fn get_used_value(&self) -> &Value {
+        unsafe { core::LLVMGetUsedValue(self.into()).into() }
+    }
+}
+/// Iterates through the uses of a value.
+pub struct UseIter<'a> {
+    current: Option<&'a Use>
+}
+impl<'a> Iterator for UseIter<'a> {
+    type Item = &'a Use;
+    fn next(&mut self) -> Option<&'a Use> {
+        let current = self.current;
+        if let Some(next) = current {
+            self.current = unsafe { util::ptr_to_null(core::LLVMGetNextUse(next.into())) };
+        }
+        current
+    }
 }
 /// Comparative operations on values.
-#[derive(Copy, Clone, Eq, PartialEq)]
+///
+/// The plain orderings compare integers as signed and floats as ordered, the
+/// `Unsigned` orderings are only valid on integers and the `Unordered` ones are
+/// only valid on floats.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum Predicate {
     Equal,
     NotEqual,
     GreaterThan,
     GreaterThanOrEqual,
     LessThan,
-    LessThanOrEqual
+    LessThanOrEqual,
+    UnsignedGreaterThan,
+    UnsignedGreaterThanOrEqual,
+    UnsignedLessThan,
+    UnsignedLessThanOrEqual,
+    /// True if neither operand is a NaN.
+    Ordered,
+    /// True if either operand is a NaN.
+    Unordered,
+    UnorderedEqual,
+    UnorderedNotEqual,
+    UnorderedGreaterThan,
+    UnorderedGreaterThanOrEqual,
+    UnorderedLessThan,
+    UnorderedLessThanOrEqual
+}
+impl From<Predicate> for LLVMIntPredicate {
+    fn from(pred: Predicate) -> LLVMIntPredicate {
+        match pred {
+            Predicate::Equal => LLVMIntPredicate::LLVMIntEQ,
+            Predicate::NotEqual => LLVMIntPredicate::LLVMIntNE,
+            Predicate::GreaterThan => LLVMIntPredicate::LLVMIntSGT,
+            Predicate::GreaterThanOrEqual => LLVMIntPredicate::LLVMIntSGE,
+            Predicate::LessThan => LLVMIntPredicate::LLVMIntSLT,
+            Predicate::LessThanOrEqual => LLVMIntPredicate::LLVMIntSLE,
+            Predicate::UnsignedGreaterThan => LLVMIntPredicate::LLVMIntUGT,
+            Predicate::UnsignedGreaterThanOrEqual => LLVMIntPredicate::LLVMIntUGE,
+            Predicate::UnsignedLessThan => LLVMIntPredicate::LLVMIntULT,
+            Predicate::UnsignedLessThanOrEqual => LLVMIntPredicate::LLVMIntULE,
+            _ => panic!("expected an integer predicate, got {:?}", pred)
+        }
+    }
+}
+impl From<Predicate> for LLVMRealPredicate {
+    fn from(pred: Predicate) -> LLVMRealPredicate {
+        match pred {
+            Predicate::Equal => LLVMRealPredicate::LLVMRealOEQ,
+            Predicate::NotEqual => LLVMRealPredicate::LLVMRealONE,
+            Predicate::GreaterThan => LLVMRealPredicate::LLVMRealOGT,
+            Predicate::GreaterThanOrEqual => LLVMRealPredicate::LLVMRealOGE,
+            Predicate::LessThan => LLVMRealPredicate::LLVMRealOLT,
+            Predicate::LessThanOrEqual => LLVMRealPredicate::LLVMRealOLE,
+            Predicate::Ordered => LLVMRealPredicate::LLVMRealORD,
+            Predicate::Unordered => LLVMRealPredicate::LLVMRealUNO,
+            Predicate::UnorderedEqual => LLVMRealPredicate::LLVMRealUEQ,
+            Predicate::UnorderedNotEqual => LLVMRealPredicate::LLVMRealUNE,
+            Predicate::UnorderedGreaterThan => LLVMRealPredicate::LLVMRealUGT,
+            Predicate::UnorderedGreaterThanOrEqual => LLVMRealPredicate::LLVMRealUGE,
+            Predicate::UnorderedLessThan => LLVMRealPredicate::LLVMRealULT,
+            Predicate::UnorderedLessThanOrEqual => LLVMRealPredicate::LLVMRealULE,
+            _ => panic!("expected a float predicate, got {:?}", pred)
+        }
+    }
+}
+/// The ordering constraints an atomic memory access has relative to other memory accesses.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum AtomicOrdering {
+    /// Not atomic at all.
+    NotAtomic                   = 0,
+    /// Atomic, but with no ordering guarantees. This matches Java's non-volatile shared variables.
+    Unordered                   = 1,
+    /// Every access to the same address happens in a single total order. This matches Rust's `Relaxed`.
+    Monotonic                   = 2,
+    /// Later accesses can't be moved before this one. Only valid on reads.
+    Acquire                     = 4,
+    /// Earlier accesses can't be moved after this one. Only valid on writes.
+    Release                     = 5,
+    /// Both `Acquire` and `Release`. Only valid on read-modify-write operations.
+    AcquireRelease              = 6,
+    /// Both `AcquireRelease` and in a single total order with all other sequentially consistent accesses.
+    SequentiallyConsistent      = 7
+}
+impl From<LLVMAtomicOrdering> for AtomicOrdering {
+    fn from(ordering: LLVMAtomicOrdering) -> AtomicOrdering {
+        unsafe { mem::transmute(ordering) }
+    }
+}
+impl From<AtomicOrdering> for LLVMAtomicOrdering {
+    fn from(ordering: AtomicOrdering) -> LLVMAtomicOrdering {
+        unsafe { mem::transmute(ordering) }
+    }
+}
+/// The operations an `atomicrmw` instruction can perform on memory.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum AtomicBinOp {
+    /// Replace the old value with the new one.
+    Xchg    = 0,
+    /// Add the new value to the old one.
+    Add     = 1,
+    /// Subtract the new value from the old one.
+    Sub     = 2,
+    /// Bitwise and the old value with the new one.
+    And     = 3,
+    /// Bitwise nand the old value with the new one.
+    Nand    = 4,
+    /// Bitwise or the old value with the new one.
+    Or      = 5,
+    /// Bitwise xor the old value with the new one.
+    Xor     = 6,
+    /// Keep the larger of the values, compared as signed integers.
+    Max     = 7,
+    /// Keep the smaller of the values, compared as signed integers.
+    Min     = 8,
+    /// Keep the larger of the values, compared as unsigned integers.
+    UMax    = 9,
+    /// Keep the smaller of the values, compared as unsigned integers.
+    UMin    = 10
+}
+impl From<LLVMAtomicRMWBinOp> for AtomicBinOp {
+    fn from(op: LLVMAtomicRMWBinOp) -> AtomicBinOp {
+        unsafe { mem::transmute(op) }
+    }
+}
+impl From<AtomicBinOp> for LLVMAtomicRMWBinOp {
+    fn from(op: AtomicBinOp) -> LLVMAtomicRMWBinOp {
+        unsafe { mem::transmute(op) }
+    }
+}
+/// The kinds of instruction there are.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum Opcode {
+    Ret             = 1,
+    Br              = 2,
+    Switch          = 3,
+    IndirectBr      = 4,
+    Invoke          = 5,
+    Unreachable     = 7,
+    Add             = 8,
+    FAdd            = 9,
+    Sub             = 10,
+    FSub            = 11,
+    Mul             = 12,
+    FMul            = 13,
+    UDiv            = 14,
+    SDiv            = 15,
+    FDiv            = 16,
+    URem            = 17,
+    SRem            = 18,
+    FRem            = 19,
+    Shl             = 20,
+    LShr            = 21,
+    AShr            = 22,
+    And             = 23,
+    Or              = 24,
+    Xor             = 25,
+    Alloca          = 26,
+    Load            = 27,
+    Store           = 28,
+    GetElementPtr   = 29,
+    Trunc           = 30,
+    ZExt            = 31,
+    SExt            = 32,
+    FPToUI          = 33,
+    FPToSI          = 34,
+    UIToFP          = 35,
+    SIToFP          = 36,
+    FPTrunc         = 37,
+    FPExt           = 38,
+    PtrToInt        = 39,
+    IntToPtr        = 40,
+    BitCast         = 41,
+    ICmp            = 42,
+    FCmp            = 43,
+    PHI             = 44,
+    Call            = 45,
+    Select          = 46,
+    UserOp1         = 47,
+    UserOp2         = 48,
+    VAArg           = 49,
+    ExtractElement  = 50,
+    InsertElement   = 51,
+    ShuffleVector   = 52,
+    ExtractValue    = 53,
+    InsertValue     = 54,
+    Fence           = 55,
+    AtomicCmpXchg   = 56,
+    AtomicRMW       = 57,
+    Resume          = 58,
+    LandingPad      = 59,
+    AddrSpaceCast   = 60,
+    CleanupRet      = 61,
+    CatchRet        = 62,
+    CatchPad        = 63,
+    CleanupPad      = 64,
+    CatchSwitch     = 65
+}
+impl From<LLVMOpcode> for Opcode {
+    fn from(opcode: LLVMOpcode) -> Opcode {
+        unsafe { mem::transmute(opcode) }
+    }
+}
+impl From<Opcode> for LLVMOpcode {
+    fn from(opcode: Opcode) -> LLVMOpcode {
+        unsafe { mem::transmute(opcode) }
+    }
+}
+impl Opcode {
+    /// Returns true if this kind of instruction ends a basic block.
+    pub fn is_terminator(self) -> bool {
+        match self {
+            Opcode::Ret | Opcode::Br | Opcode::Switch | Opcode::IndirectBr | Opcode::Invoke |
+            Opcode::Unreachable | Opcode::Resume | Opcode::CleanupRet | Opcode::CatchRet |
+            Opcode::CatchSwitch => true,
+            _ => false
+        }
+    }
 }
 /// An argument that is passed to a function.
 pub struct Arg(PhantomData<[u8]>);
@@ -133,6 +680,376 @@ impl Arg {
     }
 }
 
+/// An instruction, which is a value computed by a basic block.
+pub struct Instruction(PhantomData<[u8]>);
+native_ref!(&Instruction = LLVMValueRef);
+sub!{Instruction, LLVMIsAInstruction}
+to_str!{Instruction, LLVMPrintValueToString}
+impl Instruction {
+    /// Returns the opcode of this instruction, which says what kind of instruction it is.
+    pub fn get_opcode(&self) -> Opcode {
+        unsafe { core::LLVMGetInstructionOpcode(self.into()).into() }
+    }
+    /// Returns the number of operands this instruction has.
+    pub fn num_operands(&self) -> usize {
+        unsafe { core::LLVMGetNumOperands(self.into()) as usize }
+    }
+    /// Returns the operand at the index given.
+    pub fn get_operand(&self, index: usize) -> &Value {
+        if index < self.num_operands() {
+            unsafe { core::LLVMGetOperand(self.into(), index as c_uint).into() }
+        } else {
+            panic!("no such operand {} on {:?}", index, self)
+        }
+    }
+    /// Replace the operand at the index given with `value`.
+    pub fn set_operand(&self, index: usize, value: &Value) {
+        if index < self.num_operands() {
+            unsafe { core::LLVMSetOperand(self.into(), index as c_uint, value.into()) }
+        } else {
+            panic!("no such operand {} on {:?}", index, self)
+        }
+    }
+    /// Returns the basic block containing this instruction, or `None` if it isn't in one.
+    pub fn get_parent(&self) -> Option<&BasicBlock> {
+        unsafe { util::ptr_to_null(core::LLVMGetInstructionParent(self.into())) }
+    }
+    /// Returns the instruction after this one in its block, or `None` if this is the last one.
+    pub fn get_next(&self) -> Option<&Instruction> {
+        unsafe { util::ptr_to_null(core::LLVMGetNextInstruction(self.into())) }
+    }
+    /// Returns the instruction before this one in its block, or `None` if this is the first one.
+    pub fn get_previous(&self) -> Option<&Instruction> {
+        unsafe { util::ptr_to_null(core::LLVMGetPreviousInstruction(self.into())) }
+    }
+    /// Unlink this instruction from its basic block, but do not delete it.
+    ///
+    /// The instruction can be inserted again with `Builder::insert`. A removed instruction
+    /// can't be deleted directly, and it still counts as a use of its operands, so to free
+    /// it, insert it again and then call `erase_from_parent`. Otherwise it is leaked.
+    ///
+    /// This panics if the instruction isn't in a basic block.
+    pub fn remove_from_parent(&self) {
+        assert!(self.get_parent().is_some(), "instruction {:?} is not in a basic block", self);
+        unsafe { core::LLVMInstructionRemoveFromParent(self.into()) }
+    }
+    /// Unlink this instruction from its basic block and delete it.
+    ///
+    /// This fails with the number of remaining uses if the instruction is still used,
+    /// so those uses should be replaced with `Value::replace_all_uses_with` first. This
+    /// panics if the instruction isn't in a basic block.
+    ///
+    /// This is unsafe because there should be no other reference to this, but
+    /// this can't be guranteed using Rust semantics.
+    pub unsafe fn erase_from_parent(&self) -> Result<(), usize> {
+        assert!(self.get_parent().is_some(), "instruction {:?} is not in a basic block", self);
+        try!(check_unused(self));
+        core::LLVMInstructionEraseFromParent(self.into());
+        Ok(())
+    }
+}
+
+/// An instruction that calls a function.
+pub struct CallInst(PhantomData<[u8]>);
+native_ref!(&CallInst = LLVMValueRef);
+sub!{CallInst, LLVMIsACallInst, Instruction}
+to_str!{CallInst, LLVMPrintValueToString}
+impl CallInst {
+    /// Returns the function or function pointer this calls.
+    pub fn get_called_value(&self) -> &Value {
+        unsafe { core::LLVMGetCalledValue(self.into()).into() }
+    }
+    /// Returns true if this is a tail call.
+    pub fn is_tail_call(&self) -> bool {
+        unsafe { core::LLVMIsTailCall(self.into()) != 0 }
+    }
+    /// Set whether this is a tail call.
+    pub fn set_tail_call(&self, is_tail: bool) {
+        unsafe { core::LLVMSetTailCall(self.into(), is_tail as c_int) }
+    }
+}
+
+/// An instruction that branches to one block, or one of two blocks depending on a condition.
+pub struct BranchInst(PhantomData<[u8]>);
+native_ref!(&BranchInst = LLVMValueRef);
+sub!{BranchInst, LLVMIsABranchInst, Instruction}
+to_str!{BranchInst, LLVMPrintValueToString}
+impl BranchInst {
+    /// Returns the condition this branches on, or `None` if it always branches to the same block.
+    pub fn get_condition(&self) -> Option<&Value> {
+        unsafe {
+            if core::LLVMIsConditional(self.into()) != 0 {
+                Some(core::LLVMGetCondition(self.into()).into())
+            } else {
+                None
+            }
+        }
+    }
+    /// Returns the number of blocks this can branch to.
+    pub fn num_successors(&self) -> usize {
+        unsafe { core::LLVMGetNumSuccessors(self.into()) as usize }
+    }
+    /// Returns the block at the index given that this can branch to.
+    ///
+    /// For a conditional branch, the block at index 0 is branched to when the condition is true.
+    pub fn get_successor(&self, index: usize) -> &BasicBlock {
+        if index < self.num_successors() {
+            unsafe { core::LLVMGetSuccessor(self.into(), index as c_uint).into() }
+        } else {
+            panic!("no such successor {} on {:?}", index, self)
+        }
+    }
+}
+
+/// An instruction that returns from the function, with or without a value.
+pub struct ReturnInst(PhantomData<[u8]>);
+native_ref!(&ReturnInst = LLVMValueRef);
+sub!{ReturnInst, LLVMIsAReturnInst, Instruction}
+to_str!{ReturnInst, LLVMPrintValueToString}
+impl ReturnInst {
+    /// Returns the value this returns, or `None` if it returns void.
+    pub fn get_value(&self) -> Option<&Value> {
+        if self.num_operands() > 0 {
+            Some(self.get_operand(0))
+        } else {
+            None
+        }
+    }
+}
+
+/// An instruction that allocates memory on the stack.
+pub struct AllocaInst(PhantomData<[u8]>);
+native_ref!(&AllocaInst = LLVMValueRef);
+sub!{AllocaInst, LLVMIsAAllocaInst, Instruction}
+to_str!{AllocaInst, LLVMPrintValueToString}
+impl AllocaInst {
+    /// Returns the type this allocates memory for.
+    pub fn get_allocated_type(&self) -> &Type {
+        unsafe { core::LLVMGetAllocatedType(self.into()).into() }
+    }
+}
+
+/// An instruction that reads a value from memory.
+pub struct LoadInst(PhantomData<[u8]>);
+native_ref!(&LoadInst = LLVMValueRef);
+sub!{LoadInst, LLVMIsALoadInst, Instruction}
+to_str!{LoadInst, LLVMPrintValueToString}
+impl LoadInst {
+    /// Returns the pointer this reads from.
+    pub fn get_pointer(&self) -> &Value {
+        self.get_operand(0)
+    }
+}
+
+/// An instruction that writes a value to memory.
+pub struct StoreInst(PhantomData<[u8]>);
+native_ref!(&StoreInst = LLVMValueRef);
+sub!{StoreInst, LLVMIsAStoreInst, Instruction}
+to_str!{StoreInst, LLVMPrintValueToString}
+impl StoreInst {
+    /// Returns the value this writes.
+    pub fn get_value(&self) -> &Value {
+        self.get_operand(0)
+    }
+    /// Returns the pointer this writes to.
+    pub fn get_pointer(&self) -> &Value {
+        self.get_operand(1)
+    }
+}
+
+/// A PHI node, which selects a value depending on the predecessor of the current block.
+pub struct Phi(PhantomData<[u8]>);
+native_ref!(&Phi = LLVMValueRef);
+sub!{Phi, LLVMIsAPHINode, Instruction}
+to_str!{Phi, LLVMPrintValueToString}
+impl Phi {
+    /// Add the incoming values given to this node, where each value is
+    /// paired with the basic block it comes from.
+    pub fn add_incoming(&self, incoming: &[(&Value, &BasicBlock)]) {
+        let values:Vec<LLVMValueRef> = incoming.iter().map(|&(value, _)| value.into()).collect();
+        let blocks:Vec<LLVMBasicBlockRef> = incoming.iter().map(|&(_, block)| block.into()).collect();
+        unsafe {
+            core::LLVMAddIncoming(self.into(), values.as_ptr() as *mut LLVMValueRef, blocks.as_ptr() as *mut LLVMBasicBlockRef, incoming.len() as c_uint)
+        }
+    }
+    /// Returns the number of incoming values this node has.
+    pub fn count_incoming(&self) -> usize {
+        unsafe { core::LLVMCountIncoming(self.into()) as usize }
+    }
+    /// Returns the incoming value at the index given and the block it comes from.
+    pub fn get_incoming(&self, index: usize) -> (&Value, &BasicBlock) {
+        if index < self.count_incoming() {
+            unsafe {
+                let value = core::LLVMGetIncomingValue(self.into(), index as c_uint);
+                let block = core::LLVMGetIncomingBlock(self.into(), index as c_uint);
+                (value.into(), block.into())
+            }
+        } else {
+            panic!("no such incoming index {} on {:?}", index, self)
+        }
+    }
+    /// Iterate through the incoming values of this node.
+    pub fn incoming(&self) -> IncomingIter {
+        IncomingIter {
+            phi: self,
+            min: 0,
+            max: self.count_incoming()
+        }
+    }
+}
+impl<'a> IntoIterator for &'a Phi {
+    type IntoIter = IncomingIter<'a>;
+    type Item = (&'a Value, &'a BasicBlock);
+    fn into_iter(self) -> IncomingIter<'a> {
+        self.incoming()
+    }
+}
+/// Iterates through the incoming values of a PHI node.
+pub struct IncomingIter<'a> {
+    phi: &'a Phi,
+    min: usize,
+    max: usize
+}
+impl<'a> Iterator for IncomingIter<'a> {
+    type Item = (&'a Value, &'a BasicBlock);
+    fn next(&mut self) -> Option<(&'a Value, &'a BasicBlock)> {
+        if self.min == self.max {
+            None
+        } else {
+            self.min += 1;
+            Some(self.phi.get_incoming(self.min - 1))
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.max - self.min;
+        (len, Some(len))
+    }
+}
+impl<'a> DoubleEndedIterator for IncomingIter<'a> {
+    fn next_back(&mut self) -> Option<(&'a Value, &'a BasicBlock)> {
+        if self.min == self.max {
+            None
+        } else {
+            self.max -= 1;
+            Some(self.phi.get_incoming(self.max))
+        }
+    }
+}
+
+/// A landing pad, which is where control goes when the stack is unwound by an `invoke`.
+pub struct LandingPad(PhantomData<[u8]>);
+native_ref!(&LandingPad = LLVMValueRef);
+sub!{LandingPad, LLVMIsALandingPadInst, Instruction}
+to_str!{LandingPad, LLVMPrintValueToString}
+impl LandingPad {
+    /// Add a clause that catches exceptions with the type info `type_info`.
+    pub fn add_catch(&self, type_info: &Value) {
+        unsafe { core::LLVMAddClause(self.into(), type_info.into()) }
+    }
+    /// Add a clause that catches every exception, including foreign ones.
+    pub fn add_catch_all(&self) {
+        unsafe {
+            let i8_ptr = core::LLVMPointerType(core::LLVMInt8TypeInContext(self.get_context().into()), 0);
+            core::LLVMAddClause(self.into(), core::LLVMConstNull(i8_ptr))
+        }
+    }
+    /// Add a clause that filters exceptions, where `filter` is a constant array of the type infos
+    /// that may pass through.
+    pub fn add_filter(&self, filter: &Value) {
+        assert!(filter.get_type().is_array());
+        unsafe { core::LLVMAddClause(self.into(), filter.into()) }
+    }
+    /// Returns the number of clauses this landing pad has.
+    pub fn num_clauses(&self) -> usize {
+        unsafe { core::LLVMGetNumClauses(self.into()) as usize }
+    }
+    /// Returns the clause at the index given.
+    pub fn get_clause(&self, index: usize) -> &Value {
+        if index < self.num_clauses() {
+            unsafe { core::LLVMGetClause(self.into(), index as c_uint).into() }
+        } else {
+            panic!("no such clause {} on {:?}", index, self)
+        }
+    }
+    /// Set whether this landing pad should be entered even when no clause matches, so
+    /// it can clean up before the unwinding continues.
+    pub fn set_cleanup(&self, is_cleanup: bool) {
+        unsafe { core::LLVMSetCleanup(self.into(), is_cleanup as c_int) }
+    }
+    /// Returns true if this landing pad is a cleanup.
+    pub fn is_cleanup(&self) -> bool {
+        unsafe { core::LLVMIsCleanup(self.into()) != 0 }
+    }
+}
+
+/// A switch instruction, which branches to the block of whichever case matches its value.
+pub struct Switch(PhantomData<[u8]>);
+native_ref!(&Switch = LLVMValueRef);
+sub!{Switch, LLVMIsASwitchInst, Instruction}
+to_str!{Switch, LLVMPrintValueToString}
+impl Switch {
+    /// Add a case that branches to `dest` when the value is equal to `on`.
+    pub fn add_case(&self, on: &Value, dest: &BasicBlock) {
+        unsafe { core::LLVMAddCase(self.into(), on.into(), dest.into()) }
+    }
+    /// Add all the cases given to this switch.
+    pub fn add_cases(&self, cases: &[(&Value, &BasicBlock)]) {
+        for &(on, dest) in cases {
+            self.add_case(on, dest);
+        }
+    }
+    /// Returns the block this branches to when none of the cases match.
+    pub fn get_default(&self) -> &BasicBlock {
+        unsafe { core::LLVMGetSwitchDefaultDest(self.into()).into() }
+    }
+}
+
+/// An indirect branch instruction, which branches to the block at an address.
+pub struct IndirectBr(PhantomData<[u8]>);
+native_ref!(&IndirectBr = LLVMValueRef);
+sub!{IndirectBr, LLVMIsAIndirectBrInst, Instruction}
+to_str!{IndirectBr, LLVMPrintValueToString}
+impl IndirectBr {
+    /// Add `dest` to the blocks this can branch to.
+    pub fn add_destination(&self, dest: &BasicBlock) {
+        unsafe { core::LLVMAddDestination(self.into(), dest.into()) }
+    }
+}
+
+/// A piece of inline assembly, which can be called like a function with
+/// `Builder::build_call_asm`.
+pub struct InlineAsm(PhantomData<[u8]>);
+native_ref!(&InlineAsm = LLVMValueRef);
+sub!{InlineAsm, LLVMIsAInlineAsm}
+to_str!{InlineAsm, LLVMPrintValueToString}
+impl InlineAsm {
+    /// Create a new piece of inline assembly with the signature `sig`, which runs `asm` with
+    /// its operands bound according to `constraints`.
+    ///
+    /// If `side_effects` is true, it won't be removed or reordered even if its result is unused.
+    /// If `align_stack` is true, the stack will be aligned before it runs.
+    ///
+    /// ```rust
+    /// use llvm::*;
+    /// let context = Context::new();
+    /// let u32_t = Type::get::<u32>(&context);
+    /// let sig = FunctionType::new(StructType::new(&context, &[u32_t, u32_t], false), &[]);
+    /// let rdtsc = InlineAsm::new(sig, "rdtsc", "={eax},={edx}", true, false);
+    /// assert_eq!(rdtsc.get_signature(), sig);
+    /// ```
+    pub fn new<'a>(sig: &'a FunctionType, asm: &str, constraints: &str, side_effects: bool, align_stack: bool) -> &'a InlineAsm {
+        let (c_asm, c_constraints) = (CString::new(asm).unwrap(), CString::new(constraints).unwrap());
+        unsafe { core::LLVMConstInlineAsm(sig.into(), c_asm.as_ptr(), c_constraints.as_ptr(), side_effects as c_int, align_stack as c_int).into() }
+    }
+    /// Returns the signature of this inline assembly.
+    pub fn get_signature(&self) -> &FunctionType {
+        unsafe {
+            let ty = core::LLVMTypeOf(self.into());
+            core::LLVMGetElementType(ty).into()
+        }
+    }
+}
+
 /// A value with global scope (eg: Function, Alias, Global variable)
 pub struct GlobalValue(PhantomData<[u8]>);
 native_ref!(&GlobalValue = LLVMValueRef);
@@ -158,6 +1075,50 @@ impl GlobalValue {
             core::LLVMIsDeclaration(self.into()) == 1
         }
     }
+    /// Set the visibility of this global to other modules.
+    pub fn set_visibility(&self, vis: Visibility) {
+        unsafe { core::LLVMSetVisibility(self.into(), vis.into()) }
+    }
+    /// Returns the visibility of this global to other modules.
+    pub fn get_visibility(&self) -> Visibility {
+        unsafe { core::LLVMGetVisibility(self.into()).into() }
+    }
+    /// Set how this global is imported from or exported to a DLL.
+    pub fn set_dll_storage_class(&self, class: DllStorageClass) {
+        unsafe { core::LLVMSetDLLStorageClass(self.into(), class.into()) }
+    }
+    /// Returns how this global is imported from or exported to a DLL.
+    pub fn get_dll_storage_class(&self) -> DllStorageClass {
+        unsafe { core::LLVMGetDLLStorageClass(self.into()).into() }
+    }
+    /// Set whether the address of this global is insignificant, so it can be merged with
+    /// other globals that have the same contents.
+    pub fn set_unnamed_addr(&self, unnamed_addr: bool) {
+        unsafe { core::LLVMSetUnnamedAddr(self.into(), unnamed_addr as c_int) }
+    }
+    /// Returns true if the address of this global is insignificant.
+    pub fn has_unnamed_addr(&self) -> bool {
+        unsafe { core::LLVMHasUnnamedAddr(self.into()) != 0 }
+    }
+    /// Set the section of the object file this global is emitted to.
+    pub fn set_section(&self, section: &str) {
+        util::with_cstr(section, |ptr| unsafe {
+            core::LLVMSetSection(self.into(), ptr)
+        })
+    }
+    /// Returns the section of the object file this global is emitted to, or `None` if it
+    /// lacks an explicit section.
+    pub fn get_section(&self) -> Option<&str> {
+        unsafe {
+            util::to_null_str(core::LLVMGetSection(self.into()) as *mut c_char).and_then(|section| {
+                if section.is_empty() {
+                    None
+                } else {
+                    Some(section)
+                }
+            })
+        }
+    }
 }
 
 /// A global variable
@@ -191,6 +1152,34 @@ impl GlobalVariable {
             core::LLVMIsGlobalConstant(self.into()) != 0
         }
     }
+    /// Set the thread-local storage model of this global.
+    pub fn set_thread_local(&self, mode: ThreadLocalMode) {
+        unsafe { core::LLVMSetThreadLocalMode(self.into(), mode.into()) }
+    }
+    /// Returns the thread-local storage model of this global.
+    pub fn get_thread_local(&self) -> ThreadLocalMode {
+        unsafe { core::LLVMGetThreadLocalMode(self.into()).into() }
+    }
+    /// Set whether this global may be initialized outside of the module, so its
+    /// initializer can't be assumed to be its value when the program starts.
+    pub fn set_externally_initialized(&self, is_ext_init: bool) {
+        unsafe { core::LLVMSetExternallyInitialized(self.into(), is_ext_init as c_int) }
+    }
+    /// Returns true if this global may be initialized outside of the module.
+    pub fn is_externally_initialized(&self) -> bool {
+        unsafe { core::LLVMIsExternallyInitialized(self.into()) != 0 }
+    }
+    /// Remove this global from its module and delete it.
+    ///
+    /// This fails with the number of remaining uses if the global is still used.
+    ///
+    /// This is unsafe because there should be no other reference to this, but
+    /// this can't be guranteed using Rust semantics.
+    pub unsafe fn delete(&self) -> Result<(), usize> {
+        try!(check_unused(self));
+        core::LLVMDeleteGlobal(self.into());
+        Ok(())
+    }
 }
 
 /// An alias to another global value.
@@ -198,6 +1187,17 @@ pub struct Alias(PhantomData<[u8]>);
 native_ref!(&Alias = LLVMValueRef);
 sub!{Alias, LLVMIsAGlobalAlias, GlobalValue}
 to_str!{Alias, LLVMPrintValueToString}
+impl Alias {
+    /// Returns the value this alias refers to.
+    pub fn get_aliasee(&self) -> &Value {
+        unsafe { core::LLVMGetOperand(self.into(), 0).into() }
+    }
+    /// Make this alias refer to the constant `aliasee`, which must have the same type as it.
+    pub fn set_aliasee(&self, aliasee: &Value) {
+        assert!(aliasee.get_type() == self.get_type(), "expected aliasee of type {:?}, got {:?}", self.get_type(), aliasee.get_type());
+        unsafe { core::LLVMSetOperand(self.into(), 0, aliasee.into()) }
+    }
+}
 /// A function is a kind of value that can be called and contains blocks of code.
 ///
 /// To get the value of each argument to a function, you can use the index operator.
@@ -234,6 +1234,21 @@ impl Function {
     pub fn blocks(&self) -> BlockIter {
         BlockIter::new(self)
     }
+    /// Iterate through the instructions in all of this function's basic blocks.
+    pub fn instructions(&self) -> FunctionInstrIter {
+        FunctionInstrIter::new(self)
+    }
+    /// Remove this function from its module and delete it along with its body.
+    ///
+    /// This fails with the number of remaining uses if the function is still used.
+    ///
+    /// This is unsafe because there should be no other reference to this, but
+    /// this can't be guranteed using Rust semantics.
+    pub unsafe fn delete(&self) -> Result<(), usize> {
+        try!(check_unused(self));
+        core::LLVMDeleteFunction(self.into());
+        Ok(())
+    }
     /// Returns the entry block of this function or `None` if there is none.
     pub fn get_entry(&self) -> Option<&BasicBlock> {
         unsafe { mem::transmute(core::LLVMGetEntryBasicBlock(self.into())) }
@@ -245,6 +1260,24 @@ impl Function {
             core::LLVMGetElementType(ty).into()
         }
     }
+    /// Set the personality function used to handle exceptions unwinding through this function.
+    pub fn set_personality(&self, personality: &Function) {
+        unsafe { core::LLVMSetPersonalityFn(self.into(), personality.into()) }
+    }
+    /// Returns the personality function of this function, or `None` if it has none.
+    pub fn get_personality(&self) -> Option<&Function> {
+        unsafe {
+            if core::LLVMHasPersonalityFn(self.into()) != 0 {
+                Some(core::LLVMGetPersonalityFn(self.into()).into())
+            } else {
+                None
+            }
+        }
+    }
+    /// Set the calling convention of this function, which calls to it must match.
+    pub fn set_call_conv(&self, conv: CallConv) {
+        unsafe { core::LLVMSetFunctionCallConv(self.into(), conv as c_uint) }
+    }
     /// Add the attribute given to this function.
     pub fn add_attribute(&self, attr: Attribute) {
         unsafe { core::LLVMAddFunctionAttr(self.into(), attr.into()) }
@@ -355,6 +1388,26 @@ impl From<Attribute> for LLVMAttribute {
     }
 }
 
+/// The conventions a function can use to pass its arguments and return value.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum CallConv {
+    /// The C calling convention, which is the default.
+    C               = 0,
+    /// Makes calls as fast as possible, such as by passing arguments in registers.
+    Fast            = 8,
+    /// Makes calls as fast as possible, assuming that they will rarely happen.
+    Cold            = 9,
+    /// Used by the WebKit JavaScript engine.
+    WebKitJS        = 12,
+    /// Passes arguments in any register, for patch points.
+    AnyReg          = 13,
+    /// The `stdcall` convention on x86.
+    X86Stdcall      = 64,
+    /// The `fastcall` convention on x86.
+    X86Fastcall     = 65
+}
+
 /// A way of indicating to LLVM how you want a global to interact during linkage.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 #[repr(C)]
@@ -392,6 +1445,73 @@ impl From<Linkage> for LLVMLinkage {
         unsafe { mem::transmute(attr) }
     }
 }
+/// How visible a global is to other modules after it is linked.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum Visibility {
+    /// The global is visible to other modules, and can be overridden by them if it has weak linkage.
+    Default   = 0,
+    /// The global is not visible to other modules after being linked into a shared object.
+    Hidden    = 1,
+    /// The global is visible to other modules, but can't be overridden by them.
+    Protected = 2
+}
+impl From<LLVMVisibility> for Visibility {
+    fn from(vis: LLVMVisibility) -> Visibility {
+        unsafe { mem::transmute(vis) }
+    }
+}
+impl From<Visibility> for LLVMVisibility {
+    fn from(vis: Visibility) -> LLVMVisibility {
+        unsafe { mem::transmute(vis) }
+    }
+}
+/// How a global is imported from or exported to a DLL on Windows.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum DllStorageClass {
+    /// The global is neither imported nor exported.
+    Default = 0,
+    /// The global is imported from a DLL.
+    Import  = 1,
+    /// The global is exported from the DLL being built.
+    Export  = 2
+}
+impl From<LLVMDLLStorageClass> for DllStorageClass {
+    fn from(class: LLVMDLLStorageClass) -> DllStorageClass {
+        unsafe { mem::transmute(class) }
+    }
+}
+impl From<DllStorageClass> for LLVMDLLStorageClass {
+    fn from(class: DllStorageClass) -> LLVMDLLStorageClass {
+        unsafe { mem::transmute(class) }
+    }
+}
+/// The thread-local storage model of a global variable.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum ThreadLocalMode {
+    /// The global is shared between all threads.
+    NotThreadLocal = 0,
+    /// Each thread has its own copy of the global, which can be accessed from any module.
+    GeneralDynamic = 1,
+    /// Each thread has its own copy of the global, which is only accessed from its own module.
+    LocalDynamic   = 2,
+    /// Each thread has its own copy of the global, which is in a module loaded at startup.
+    InitialExec    = 3,
+    /// Each thread has its own copy of the global, which is in the executable itself.
+    LocalExec      = 4
+}
+impl From<LLVMThreadLocalMode> for ThreadLocalMode {
+    fn from(mode: LLVMThreadLocalMode) -> ThreadLocalMode {
+        unsafe { mem::transmute(mode) }
+    }
+}
+impl From<ThreadLocalMode> for LLVMThreadLocalMode {
+    fn from(mode: ThreadLocalMode) -> LLVMThreadLocalMode {
+        unsafe { mem::transmute(mode) }
+    }
+}
 
 impl GetContext for Value {
     fn get_context(&self) -> &Context {
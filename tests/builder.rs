@@ -0,0 +1,694 @@
+extern crate llvm;
+extern crate libc;
+use libc::c_char;
+use llvm::*;
+#[test]
+fn test_phi_incoming() {
+    let ctx = Context::new();
+    let module = Module::new("phi", &ctx);
+    let func = module.add_function("choose", Type::get::<fn(bool) -> u32>(&ctx));
+    let entry = func.append("entry");
+    let on_true = func.append("on_true");
+    let on_false = func.append("on_false");
+    let merge = func.append("merge");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_cond_br(&func[0], on_true, Some(on_false));
+    builder.position_at_end(on_true);
+    builder.build_br(merge);
+    builder.position_at_end(on_false);
+    builder.build_br(merge);
+    builder.position_at_end(merge);
+    let one = 1u32.compile(&ctx);
+    let two = 2u32.compile(&ctx);
+    let phi = builder.build_phi(Type::get::<u32>(&ctx));
+    phi.add_incoming(&[(one, on_true), (two, on_false)]);
+    builder.build_ret(phi);
+    module.verify().unwrap();
+    assert_eq!(phi.count_incoming(), 2);
+    assert!(phi.get_incoming(0) == (one, on_true));
+    let blocks:Vec<_> = phi.incoming().rev().map(|(_, block)| block).collect();
+    assert!(blocks == vec![on_false, on_true]);
+}
+
+#[test]
+fn test_exception_handling() {
+    let ctx = Context::new();
+    let module = Module::new("exceptions", &ctx);
+    let personality = module.add_function("__gxx_personality_v0", Type::get::<fn() -> i32>(&ctx));
+    let may_throw = module.add_function("may_throw", Type::get::<fn() -> ()>(&ctx));
+    let typeinfo: &Value = module.add_global("typeinfo", Type::get::<u8>(&ctx));
+    let func = module.add_function("guarded", Type::get::<fn() -> ()>(&ctx));
+    assert!(func.get_personality().is_none());
+    func.set_personality(personality);
+    assert!(func.get_personality() == Some(&*personality));
+    let entry = func.append("entry");
+    let returned = func.append("returned");
+    let unwound = func.append("unwound");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_invoke(may_throw, &[], returned, unwound);
+    builder.position_at_end(returned);
+    builder.build_ret_void();
+    builder.position_at_end(unwound);
+    let ty = StructType::new(&ctx, &[typeinfo.get_type(), Type::get::<i32>(&ctx)], false);
+    let landing_pad = builder.build_landing_pad(ty, None);
+    assert_eq!(landing_pad.num_clauses(), 0);
+    assert!(!landing_pad.is_cleanup());
+    landing_pad.add_catch(typeinfo);
+    landing_pad.add_filter(Value::new_array(typeinfo.get_type(), &[typeinfo]));
+    landing_pad.set_cleanup(true);
+    builder.build_resume(landing_pad);
+    module.verify().unwrap();
+    assert_eq!(landing_pad.num_clauses(), 2);
+    assert!(landing_pad.get_clause(0) == typeinfo);
+    assert!(landing_pad.get_clause(1).get_type().is_array());
+    assert!(landing_pad.is_cleanup());
+}
+#[test]
+fn test_atomic_memory_access() {
+    let ctx = Context::new();
+    let module = Module::new("atomic", &ctx);
+    let counter = module.add_global_variable("counter", 0u64.compile(&ctx));
+    let func = module.add_function("bump", Type::get::<fn() -> u64>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let old = builder.build_atomic_rmw(AtomicBinOp::Add, counter, 1u64.compile(&ctx), AtomicOrdering::SequentiallyConsistent);
+    let load = builder.build_load(counter);
+    load.set_volatile(true);
+    load.set_alignment(8);
+    load.set_ordering(AtomicOrdering::Acquire);
+    builder.build_fence(AtomicOrdering::SequentiallyConsistent);
+    builder.build_ret(builder.build_add(old, load));
+    module.verify().unwrap();
+    assert!(load.is_volatile());
+    assert_eq!(load.get_alignment(), 8);
+    assert_eq!(load.get_ordering(), AtomicOrdering::Acquire);
+}
+
+#[test]
+fn test_save_position() {
+    let ctx = Context::new();
+    let module = Module::new("position", &ctx);
+    let func = module.add_function("main", Type::get::<fn() -> u32>(&ctx));
+    let entry = func.append("entry");
+    let body = func.append("body");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let jump = builder.build_br(body);
+    builder.position_at_end(body);
+    let slot = {
+        let _guard = builder.save_position();
+        builder.position_before(Instruction::from_super(jump).unwrap());
+        assert!(builder.get_insert_block() == Some(entry));
+        builder.build_alloca(Type::get::<u32>(&ctx))
+    };
+    assert!(builder.get_insert_block() == Some(body));
+    builder.build_store(7u32.compile(&ctx), slot);
+    builder.build_ret(builder.build_load(slot));
+    module.verify().unwrap();
+    assert_eq!(entry.get_first().unwrap().get_opcode(), Opcode::Alloca);
+}
+
+#[test]
+fn test_save_position_mid_block() {
+    let ctx = Context::new();
+    let module = Module::new("position_mid_block", &ctx);
+    let func = module.add_function("main", Type::get::<fn(u32) -> u32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let ret = builder.build_ret(&func[0]);
+    builder.position_before(Instruction::from_super(ret).unwrap());
+    let slot = {
+        let _guard = builder.save_position();
+        builder.position_at(entry, entry.get_first().unwrap());
+        builder.build_alloca(Type::get::<u32>(&ctx))
+    };
+    builder.build_store(&func[0], slot);
+    module.verify().unwrap();
+    let opcodes: Vec<_> = entry.into_iter().map(|instr| instr.get_opcode()).collect();
+    assert_eq!(opcodes, vec![Opcode::Alloca, Opcode::Store, Opcode::Ret]);
+}
+#[test]
+#[should_panic]
+fn test_position_at_other_block() {
+    let ctx = Context::new();
+    let module = Module::new("position_other_block", &ctx);
+    let func = module.add_function("main", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let other = func.append("other");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_ret_void();
+    builder.position_at(other, entry.get_first().unwrap());
+}
+#[test]
+fn test_named_values() {
+    let ctx = Context::new();
+    let module = Module::new("named", &ctx);
+    let func = module.add_function("add", Type::get::<fn(u32, u32) -> u32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sum = builder.build_add(func[0].named("a"), func[1].named("b")).named("sum");
+    builder.build_ret(sum);
+    module.verify().unwrap();
+    assert_eq!(sum.get_name(), Some("sum"));
+    assert!(format!("{:?}", module).contains("%sum = add i32 %a, %b"));
+}
+
+#[test]
+fn test_terminators() {
+    let ctx = Context::new();
+    let module = Module::new("terminators", &ctx);
+    let func = module.add_function("pick", Type::get::<fn(u8) -> u8>(&ctx));
+    let entry = func.append("entry");
+    let on_zero = func.append("on_zero");
+    let on_one = func.append("on_one");
+    let jump = func.append("jump");
+    let unreachable = func.append("unreachable");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let switch = builder.build_switch(&func[0], unreachable, &[(0u8.compile(&ctx), on_zero)]);
+    switch.add_case(1u8.compile(&ctx), on_one);
+    assert!(switch.get_default() == unreachable);
+    builder.position_at_end(on_zero);
+    builder.build_ret(0u8.compile(&ctx));
+    builder.position_at_end(on_one);
+    builder.build_br(jump);
+    builder.position_at_end(jump);
+    let br = builder.build_indirect_br(Value::new_block_address(func, on_zero), &[]);
+    br.add_destination(on_zero);
+    builder.position_at_end(unreachable);
+    builder.build_unreachable();
+    module.verify().unwrap();
+}
+
+#[test]
+fn test_memory_intrinsics() {
+    let ctx = Context::new();
+    let module = Module::new("memory", &ctx);
+    let func = module.add_function("copy", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let ty = Type::get::<(u64, u64)>(&ctx);
+    let a = builder.build_alloca(ty);
+    let b = builder.build_alloca(ty);
+    let len = 16u64.compile(&ctx);
+    builder.build_lifetime_start(a, 16);
+    builder.build_memset(a, 0u8.compile(&ctx), len, 8, false);
+    builder.build_memcpy(b, a, len, 8, false);
+    builder.build_memmove(a, b, len, 8, true);
+    builder.build_lifetime_end(a, 16);
+    builder.build_ret_void();
+    module.verify().unwrap();
+    assert!(module.get_function("llvm.memcpy.p0i8.p0i8.i64").is_some());
+    assert!(module.get_function("llvm.memset.p0i8.i64").is_some());
+}
+
+#[test]
+fn test_memory_intrinsics_address_space() {
+    let ctx = Context::new();
+    let module = Module::new("memory_address_space", &ctx);
+    let ty = Type::get::<(u64, u64)>(&ctx);
+    let shared = module.add_global_in_address_space("shared", ty, AddressSpace::Shared);
+    shared.set_initializer(Value::new_null(ty));
+    let func = module.add_function("copy", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let local = builder.build_alloca(ty);
+    let len = 16u64.compile(&ctx);
+    builder.build_memset(shared, 0u8.compile(&ctx), len, 8, false);
+    builder.build_memcpy(local, shared, len, 8, false);
+    builder.build_ret_void();
+    module.verify().unwrap();
+    assert!(module.get_function("llvm.memset.p3i8.i64").is_some());
+    assert!(module.get_function("llvm.memcpy.p0i8.p3i8.i64").is_some());
+    assert!(!format!("{:?}", module).contains("addrspacecast"));
+}
+
+#[test]
+fn test_checked_arithmetic() {
+    let ctx = Context::new();
+    let module = Module::new("checked", &ctx);
+    let func = module.add_function("add", Type::get::<fn(u32, u32) -> u32>(&ctx));
+    let entry = func.append("entry");
+    let trap = func.append("trap");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(trap);
+    builder.build_call(module.get_intrinsic(Intrinsic::Trap, &[]), &[]);
+    builder.build_unreachable();
+    builder.position_at_end(entry);
+    let (sum, overflow) = builder.build_checked_add(&func[0], &func[1], false);
+    let next = builder.build_overflow_check(overflow, trap);
+    assert!(builder.get_insert_block() == Some(next));
+    builder.build_ret(sum);
+    module.verify().unwrap();
+    assert!(module.get_function("llvm.uadd.with.overflow.i32").is_some());
+}
+
+#[test]
+fn test_gep_indices() {
+    let ctx = Context::new();
+    let module = Module::new("gep", &ctx);
+    let func = module.add_function("second", Type::get::<fn() -> u64>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let pair = builder.build_alloca(Type::get::<(u32, u64)>(&ctx));
+    let by_field = builder.build_struct_gep(pair, 1);
+    let by_index = builder.build_gep_indices(pair, &[0, 1]);
+    assert_eq!(by_field.get_type(), by_index.get_type());
+    builder.build_ret(builder.build_load(by_index));
+    module.verify().unwrap();
+}
+
+#[test]
+fn test_variadic_function() {
+    let ctx = Context::new();
+    let module = Module::new("variadic", &ctx);
+    let printf_t = Type::get::<unsafe extern fn(*const c_char, ...) -> i32>(&ctx);
+    assert!(FunctionType::from_super(printf_t).unwrap().is_variadic());
+    let sum_t = FunctionType::new_variadic(Type::get::<u32>(&ctx), &[Type::get::<u32>(&ctx)]);
+    let func = module.add_function("sum", sum_t);
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    // The x86-64 `va_list`, which `va_start` fills in completely.
+    let byte_ptr_t = PointerType::new(Type::get::<u8>(&ctx));
+    let u32_t = Type::get::<u32>(&ctx);
+    let va_list_t = ArrayType::new(StructType::new(&ctx, &[u32_t, u32_t, byte_ptr_t, byte_ptr_t], false), 1);
+    let list = builder.build_alloca(va_list_t);
+    builder.build_va_start(list);
+    let next = builder.build_va_arg(list, Type::get::<u32>(&ctx));
+    builder.build_va_end(list);
+    builder.build_ret(builder.build_add(&func[0], next));
+    module.verify().unwrap();
+}
+
+#[test]
+fn test_variadic_calls() {
+    let ctx = Context::new();
+    let module = Module::new("variadic_calls", &ctx);
+    let printf_t = Type::get::<unsafe extern fn(*const c_char, ...) -> i32>(&ctx);
+    let printf = module.add_function("printf", printf_t);
+    let func = module.add_function("print_twice", Type::get::<fn(u32) -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let text = module.add_global_variable("format", Value::new_string(&ctx, "%u\n", false));
+    let format = builder.build_gep_indices(text, &[0, 0]);
+    builder.build_call(printf, &[format, &func[0]]);
+    let slot = builder.build_alloca(printf.get_type());
+    builder.build_store(printf, slot);
+    let callee = builder.build_load(slot);
+    let sig = FunctionType::from_super(printf_t).unwrap();
+    builder.build_call_indirect(callee, sig, &[format, &func[0], &func[0]], CallConv::C);
+    builder.build_ret_void();
+    module.verify().unwrap();
+}
+#[test]
+fn test_instruction_introspection() {
+    let ctx = Context::new();
+    let module = Module::new("introspection", &ctx);
+    let func = module.add_function("double", Type::get::<fn(u32) -> u32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sum = builder.build_add(&func[0], &func[0]);
+    builder.build_ret(sum);
+    module.verify().unwrap();
+    let add = entry.get_first().unwrap();
+    assert_eq!(add.get_opcode(), Opcode::Add);
+    assert_eq!(add.num_operands(), 2);
+    assert!(add.get_operand(1) == &*func[0]);
+    assert!(add.get_parent() == Some(entry));
+    let ret = add.get_next().unwrap();
+    assert!(ret.get_opcode().is_terminator());
+    assert!(entry.get_terminator() == Some(ret));
+    assert!(ReturnInst::from_super(ret).unwrap().get_value() == Some(sum));
+    add.set_operand(1, 1u32.compile(&ctx));
+    assert!(ret.get_previous() == Some(add));
+    module.verify().unwrap();
+}
+#[test]
+fn test_instruction_iteration() {
+    let ctx = Context::new();
+    let module = Module::new("iteration", &ctx);
+    let func = module.add_function("abs", Type::get::<fn(i32) -> i32>(&ctx));
+    assert_eq!(func.instructions().count(), 0);
+    let entry = func.append("entry");
+    let negative = func.append("negative");
+    let positive = func.append("positive");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let is_negative = builder.build_cmp(&func[0], 0i32.compile(&ctx), Predicate::LessThan);
+    builder.build_cond_br(is_negative, negative, Some(positive));
+    builder.position_at_end(negative);
+    builder.build_ret(builder.build_neg(&func[0]));
+    builder.position_at_end(positive);
+    builder.build_ret(&func[0]);
+    module.verify().unwrap();
+    let opcodes: Vec<_> = entry.into_iter().map(|instr| instr.get_opcode()).collect();
+    assert_eq!(opcodes, vec![Opcode::ICmp, Opcode::Br]);
+    assert!(entry.into_iter().next_back() == entry.get_terminator());
+    assert_eq!(func.blocks().count(), 3);
+    let opcodes: Vec<_> = func.instructions().rev().map(|instr| instr.get_opcode()).collect();
+    assert_eq!(opcodes, vec![Opcode::Ret, Opcode::Ret, Opcode::Sub, Opcode::Br, Opcode::ICmp]);
+}
+#[test]
+fn test_uses() {
+    let ctx = Context::new();
+    let module = Module::new("uses", &ctx);
+    let func = module.add_function("square_plus", Type::get::<fn(u32, u32) -> u32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let square = builder.build_mul(&func[0], &func[0]);
+    let sum = builder.build_add(square, &func[1]);
+    builder.build_ret(sum);
+    assert_eq!(func[0].num_uses(), 2);
+    assert!(square.has_one_use());
+    let user = square.uses().next().unwrap();
+    assert!(user.get_user() == sum);
+    assert!(user.get_used_value() == square);
+    square.replace_all_uses_with(&func[0]);
+    assert_eq!(square.num_uses(), 0);
+    assert_eq!(func[0].num_uses(), 3);
+    assert!(func[1].has_one_use());
+    module.verify().unwrap();
+}
+#[test]
+fn test_erase_instructions() {
+    let ctx = Context::new();
+    let module = Module::new("erase", &ctx);
+    let helper = module.add_function("helper", Type::get::<fn() -> ()>(&ctx));
+    let func = module.add_function("add_one", Type::get::<fn(u32) -> u32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sum = builder.build_add(&func[0], 1u32.compile(&ctx));
+    let ret = builder.build_ret(sum);
+    let sum = Instruction::from_super(sum).unwrap();
+    let ret = Instruction::from_super(ret).unwrap();
+    unsafe {
+        assert_eq!(sum.erase_from_parent(), Err(1));
+    }
+    ret.remove_from_parent();
+    assert!(ret.get_parent().is_none());
+    builder.position_at_end(entry);
+    builder.insert(ret);
+    assert!(entry.get_terminator() == Some(ret));
+    let product = builder.build_mul(&func[0], &func[0]);
+    let product = Instruction::from_super(product).unwrap();
+    unsafe {
+        assert_eq!(product.erase_from_parent(), Ok(()));
+        assert_eq!(helper.delete(), Ok(()));
+    }
+    assert_eq!(entry.into_iter().count(), 2);
+    assert!(module.get_function("helper").is_none());
+    module.verify().unwrap();
+}
+#[test]
+fn test_erase_removed_instruction() {
+    let ctx = Context::new();
+    let module = Module::new("erase_removed", &ctx);
+    let func = module.add_function("identity", Type::get::<fn(u32) -> u32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let square = builder.build_mul(&func[0], &func[0]);
+    builder.build_ret(&func[0]);
+    let square = Instruction::from_super(square).unwrap();
+    square.remove_from_parent();
+    assert_eq!(func[0].num_uses(), 3);
+    builder.position_at_end(entry);
+    builder.insert(square);
+    unsafe {
+        assert_eq!(square.erase_from_parent(), Ok(()));
+    }
+    assert_eq!(func[0].num_uses(), 1);
+    module.verify().unwrap();
+}
+#[test]
+#[should_panic]
+fn test_remove_detached_instruction() {
+    let ctx = Context::new();
+    let module = Module::new("remove_detached", &ctx);
+    let func = module.add_function("identity", Type::get::<fn(u32) -> u32>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    let square = builder.build_mul(&func[0], &func[0]);
+    let square = Instruction::from_super(square).unwrap();
+    square.remove_from_parent();
+    square.remove_from_parent();
+}
+#[test]
+fn test_cast_opcodes() {
+    let ctx = Context::new();
+    let module = Module::new("casts", &ctx);
+    let func = module.add_function("convert", Type::get::<fn(i32, f32, *const c_char) -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let (int, float, ptr) = (&*func[0], &*func[1], &*func[2]);
+    let (i8_t, i32_t, i64_t) = (Type::get::<i8>(&ctx), Type::get::<i32>(&ctx), Type::get::<i64>(&ctx));
+    let (f32_t, f64_t) = (Type::get::<f32>(&ctx), Type::get::<f64>(&ctx));
+    let opcode = |value: &Value| Instruction::from_super(value).unwrap().get_opcode();
+    assert!(builder.build_cast(int, i32_t, true) == int);
+    assert_eq!(opcode(builder.build_cast(int, i8_t, true)), Opcode::Trunc);
+    assert_eq!(opcode(builder.build_cast(int, i64_t, true)), Opcode::SExt);
+    assert_eq!(opcode(builder.build_cast(int, i64_t, false)), Opcode::ZExt);
+    let double = builder.build_cast(float, f64_t, true);
+    assert_eq!(opcode(double), Opcode::FPExt);
+    assert_eq!(opcode(builder.build_cast(double, f32_t, true)), Opcode::FPTrunc);
+    assert_eq!(opcode(builder.build_cast(int, f64_t, true)), Opcode::SIToFP);
+    assert_eq!(opcode(builder.build_cast(int, f64_t, false)), Opcode::UIToFP);
+    assert_eq!(opcode(builder.build_cast(float, i32_t, true)), Opcode::FPToSI);
+    assert_eq!(opcode(builder.build_cast(float, i32_t, false)), Opcode::FPToUI);
+    let addr = builder.build_cast(ptr, i64_t, false);
+    assert_eq!(opcode(addr), Opcode::PtrToInt);
+    assert_eq!(opcode(builder.build_cast(addr, PointerType::new(i32_t), false)), Opcode::IntToPtr);
+    assert_eq!(opcode(builder.build_cast(ptr, PointerType::new(i32_t), false)), Opcode::BitCast);
+    // Casting the global itself would be folded into a constant, so load a pointer to it.
+    let global = module.add_global_in_address_space("shared", i32_t, AddressSpace::Shared);
+    let shared = builder.build_load(builder.build_alloca(global.get_type()));
+    assert_eq!(opcode(builder.build_cast(shared, PointerType::new(i32_t), false)), Opcode::AddrSpaceCast);
+    let ints = builder.build_splat(int, 4);
+    assert_eq!(opcode(builder.build_cast(ints, VectorType::new(f32_t, 4), true)), Opcode::SIToFP);
+    builder.build_ret_void();
+    module.verify().unwrap();
+}
+#[test]
+#[should_panic]
+fn test_cast_vector_length_mismatch() {
+    let ctx = Context::new();
+    let module = Module::new("casts", &ctx);
+    let func = module.add_function("convert", Type::get::<fn(i32) -> ()>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    let ints = builder.build_splat(&func[0], 4);
+    builder.build_cast(ints, VectorType::new(Type::get::<i64>(&ctx), 2), true);
+}
+#[test]
+fn test_indirect_call() {
+    let ctx = Context::new();
+    let module = Module::new("indirect", &ctx);
+    let double = module.add_function("double", Type::get::<fn(u32) -> u32>(&ctx));
+    double.set_call_conv(CallConv::Fast);
+    let func = module.add_function("call_double", Type::get::<fn(u32) -> u32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let slot = builder.build_alloca(double.get_type());
+    builder.build_store(double, slot);
+    let callee = builder.build_load(slot);
+    let sig = FunctionType::from_super(Type::get::<fn(u32) -> u32>(&ctx)).unwrap();
+    let result = builder.build_call_indirect(callee, sig, &[&func[0]], CallConv::Fast);
+    assert!(format!("{:?}", result).contains("call fastcc"));
+    let address = builder.build_ptr_to_int(callee, Type::get::<usize>(&ctx));
+    builder.build_call_indirect(address, sig, &[result], CallConv::Fast);
+    builder.build_ret(result);
+    module.verify().unwrap();
+}
+#[test]
+#[should_panic]
+fn test_indirect_call_argument_types() {
+    let ctx = Context::new();
+    let module = Module::new("indirect", &ctx);
+    let double = module.add_function("double", Type::get::<fn(u32) -> u32>(&ctx));
+    let func = module.add_function("call_double", Type::get::<fn(u64) -> ()>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    let sig = FunctionType::from_super(Type::get::<fn(u32) -> u32>(&ctx)).unwrap();
+    builder.build_call_indirect(double, sig, &[&func[0]], CallConv::C);
+}
+#[test]
+fn test_unsigned_arithmetic() {
+    let ctx = Context::new();
+    let module = Module::new("unsigned", &ctx);
+    let func = module.add_function("hash", Type::get::<fn(u64, u64, f64) -> bool>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let (a, b) = (&*func[0], &*func[1]);
+    let quotient = builder.build_udiv(a, b);
+    let remainder = builder.build_urem(quotient, b);
+    let shifted = builder.build_lshr(remainder, 3u64.compile(&ctx));
+    let mixed = builder.build_xor(shifted, a);
+    let less = builder.build_cmp(mixed, b, Predicate::UnsignedLessThan);
+    let nan = builder.build_cmp(&func[2], &func[2], Predicate::Unordered);
+    builder.build_ret(builder.build_or(less, nan));
+    module.verify().unwrap();
+    let ir = format!("{:?}", module);
+    for op in &["udiv", "urem", "lshr", "xor", "icmp ult", "fcmp uno"] {
+        assert!(ir.contains(op), "expected {} in {}", op, ir);
+    }
+}
+#[test]
+#[should_panic]
+fn test_float_predicate_on_integers() {
+    let ctx = Context::new();
+    let module = Module::new("predicates", &ctx);
+    let func = module.add_function("compare", Type::get::<fn(u64, u64) -> bool>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    builder.build_cmp(&func[0], &func[1], Predicate::Unordered);
+}
+#[test]
+#[should_panic]
+fn test_integer_predicate_on_floats() {
+    let ctx = Context::new();
+    let module = Module::new("predicates", &ctx);
+    let func = module.add_function("compare", Type::get::<fn(f64, f64) -> bool>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    builder.build_cmp(&func[0], &func[1], Predicate::UnsignedLessThan);
+}
+#[test]
+fn test_vector_shuffles() {
+    let ctx = Context::new();
+    let module = Module::new("vectors", &ctx);
+    let vec_t = Type::get::<[f32; 4]>(&ctx);
+    let func = module.add_function("interleave", Type::get::<fn([f32; 4], f32) -> [f32; 4]>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let scale = builder.build_splat(&func[1], 4);
+    assert_eq!(scale.get_type(), vec_t);
+    let mask = Value::new_shuffle_mask(&ctx, &[0, 4, 1, 5]);
+    let mixed = builder.build_shuffle_vector(&func[0], scale, mask);
+    assert_eq!(mixed.get_type(), vec_t);
+    let last = builder.build_extract_element(mixed, 3u32.compile(&ctx));
+    let mixed = builder.build_insert_element(mixed, last, 0u32.compile(&ctx));
+    let ones = Value::new_splat(1f32.compile(&ctx), 4);
+    assert_eq!(ones.get_type(), vec_t);
+    builder.build_ret(builder.build_add(mixed, ones));
+    module.verify().unwrap();
+}
+#[test]
+fn test_inline_asm_call() {
+    let ctx = Context::new();
+    let module = Module::new("asm", &ctx);
+    let func = module.add_function("read_timestamp", Type::get::<fn() -> u64>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let (u32_t, u64_t) = (Type::get::<u32>(&ctx), Type::get::<u64>(&ctx));
+    let sig = FunctionType::new(StructType::new(&ctx, &[u32_t, u32_t], false), &[]);
+    let rdtsc = InlineAsm::new(sig, "rdtsc", "={eax},={edx}", true, false);
+    let parts = builder.build_call_asm(rdtsc, &[]);
+    let low = builder.build_zext(builder.build_extract_value(parts, 0), u64_t);
+    let high = builder.build_zext(builder.build_extract_value(parts, 1), u64_t);
+    let high = builder.build_shl(high, 32u64.compile(&ctx));
+    builder.build_ret(builder.build_or(high, low));
+    module.verify().unwrap();
+    assert!(format!("{:?}", module).contains("asm sideeffect \"rdtsc\""));
+}
+
+#[test]
+fn test_arithmetic_flags() {
+    let ctx = Context::new();
+    let module = Module::new("flags", &ctx);
+    let func = module.add_function("scale", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let (a, b) = (&*func[0], &*func[1]);
+    let sum = builder.build_nsw_add(a, b);
+    let product = builder.build_nuw_mul(sum, b);
+    let difference = builder.build_nsw_sub(product, a);
+    let negated = builder.build_nsw_neg(difference);
+    builder.build_ret(builder.build_exact_div(negated, b));
+    module.verify().unwrap();
+    let ir = format!("{:?}", module);
+    for op in &["add nsw", "mul nuw", "sub nsw", "sdiv exact"] {
+        assert!(ir.contains(op), "expected {} in {}", op, ir);
+    }
+}
+
+#[test]
+fn test_heap_allocation() {
+    let ctx = Context::new();
+    let module = Module::new("heap", &ctx);
+    let func = module.add_function("churn", Type::get::<fn(u32) -> u64>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let single = builder.build_malloc(Type::get::<u64>(&ctx));
+    builder.build_store(42u64.compile(&ctx), single);
+    let array = builder.build_array_malloc(Type::get::<u64>(&ctx), &func[0]);
+    let value = builder.build_load(single);
+    builder.build_store(value, array);
+    builder.build_free(single);
+    builder.build_free(array);
+    builder.build_ret(value);
+    module.verify().unwrap();
+    assert!(module.get_function("malloc").is_some());
+    assert!(module.get_function("free").is_some());
+}
+
+#[test]
+fn test_compare_exchange() {
+    let ctx = Context::new();
+    let module = Module::new("cmpxchg", &ctx);
+    let lock = module.add_global_variable("lock", 0u32.compile(&ctx));
+    let func = module.add_function("try_lock", Type::get::<fn() -> bool>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let result = builder.build_cmpxchg(lock, 0u32.compile(&ctx), 1u32.compile(&ctx), AtomicOrdering::AcquireRelease, AtomicOrdering::Acquire);
+    builder.build_ret(builder.build_extract_value(result, 1));
+    module.verify().unwrap();
+    assert!(format!("{:?}", module).contains("cmpxchg"));
+}
+
+#[test]
+#[should_panic]
+fn test_compare_exchange_release_on_failure() {
+    let ctx = Context::new();
+    let module = Module::new("cmpxchg", &ctx);
+    let lock = module.add_global_variable("lock", 0u32.compile(&ctx));
+    let func = module.add_function("unlock", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_cmpxchg(lock, 1u32.compile(&ctx), 0u32.compile(&ctx), AtomicOrdering::SequentiallyConsistent, AtomicOrdering::Release);
+}
+
+#[test]
+#[should_panic]
+fn test_compare_exchange_stronger_failure() {
+    let ctx = Context::new();
+    let module = Module::new("cmpxchg", &ctx);
+    let lock = module.add_global_variable("lock", 0u32.compile(&ctx));
+    let func = module.add_function("lock", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_cmpxchg(lock, 0u32.compile(&ctx), 1u32.compile(&ctx), AtomicOrdering::Monotonic, AtomicOrdering::SequentiallyConsistent);
+}
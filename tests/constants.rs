@@ -0,0 +1,38 @@
+extern crate llvm;
+use llvm::*;
+#[test]
+fn test_constant_folding() {
+    let ctx = Context::new();
+    let six = 6u32.compile(&ctx);
+    let seven = 7u32.compile(&ctx);
+    let product = Value::const_mul(six, seven);
+    assert!(product.is_constant());
+    assert_eq!(product.get_zext_value(), Some(42));
+    assert_eq!(Value::const_neg(product).get_sext_value(), Some(-42));
+    assert_eq!(Value::const_cmp(six, seven, Predicate::UnsignedLessThan).get_zext_value(), Some(1));
+    let wide = Value::const_zext(seven, Type::get::<u64>(&ctx));
+    assert_eq!(wide.get_type(), Type::get::<u64>(&ctx));
+    let half = Value::const_div(1f64.compile(&ctx), 2f64.compile(&ctx));
+    assert_eq!(half.get_double_value(), Some(0.5));
+    assert_eq!(half.get_zext_value(), None);
+}
+
+#[test]
+fn test_special_constants() {
+    let ctx = Context::new();
+    let u8_t = Type::get::<u8>(&ctx);
+    assert!(Value::new_null(u8_t).is_null());
+    assert_eq!(Value::new_all_ones(u8_t).get_zext_value(), Some(255));
+    assert!(Value::new_undef(u8_t).is_undef());
+    let ptr = Value::new_null_pointer(PointerType::new(u8_t));
+    assert!(ptr.is_null());
+    let array = Value::new_array(u8_t, &[1u8.compile(&ctx), 2u8.compile(&ctx)]);
+    assert!(array.get_type().is_array());
+    assert!(!array.is_null());
+    let module = Module::new("constants", &ctx);
+    let global = module.add_global_variable("bytes", array);
+    let zero = 0u32.compile(&ctx);
+    let second = Value::const_in_bounds_gep(global, &[zero, 1u32.compile(&ctx)]);
+    assert!(second.is_constant());
+    assert_eq!(second.get_type(), PointerType::new(u8_t));
+}
@@ -0,0 +1,48 @@
+extern crate llvm;
+use llvm::*;
+#[test]
+fn test_global_properties() {
+    let ctx = Context::new();
+    let module = Module::new("globals", &ctx);
+    let cache = module.add_global_variable("cache", 0u64.compile(&ctx));
+    assert_eq!(cache.get_thread_local(), ThreadLocalMode::NotThreadLocal);
+    cache.set_thread_local(ThreadLocalMode::InitialExec);
+    assert_eq!(cache.get_thread_local(), ThreadLocalMode::InitialExec);
+    cache.set_alignment(64);
+    assert_eq!(cache.get_alignment(), 64);
+    assert_eq!(cache.get_section(), None);
+    cache.set_section(".tdata.cache");
+    assert_eq!(cache.get_section(), Some(".tdata.cache"));
+    assert_eq!(cache.get_visibility(), Visibility::Default);
+    cache.set_visibility(Visibility::Hidden);
+    assert_eq!(cache.get_visibility(), Visibility::Hidden);
+    cache.set_dll_storage_class(DllStorageClass::Export);
+    assert_eq!(cache.get_dll_storage_class(), DllStorageClass::Export);
+    assert!(!cache.has_unnamed_addr());
+    cache.set_unnamed_addr(true);
+    assert!(cache.has_unnamed_addr());
+    assert!(!cache.is_externally_initialized());
+    cache.set_externally_initialized(true);
+    assert!(cache.is_externally_initialized());
+    module.verify().unwrap();
+}
+
+#[test]
+fn test_module_symbols() {
+    let ctx = Context::new();
+    let module = Module::new("symbols", &ctx);
+    assert_eq!(module.globals().count(), 0);
+    let first = module.add_global_variable("first", 1u32.compile(&ctx));
+    let second = module.add_global_variable("second", 2u32.compile(&ctx));
+    let names: Vec<_> = module.globals().map(|global| global.get_name()).collect();
+    assert_eq!(names, vec![Some("first"), Some("second")]);
+    assert!(module.globals().next_back() == Some(second));
+    module.add_function("func", Type::get::<fn() -> ()>(&ctx));
+    let names: Vec<_> = module.into_iter().map(|func| func.get_name()).collect();
+    assert_eq!(names, vec![Some("func")]);
+    let alias = module.add_global_alias("alias", first);
+    assert!(alias.get_aliasee() == &***first);
+    alias.set_aliasee(second);
+    assert!(alias.get_aliasee() == &***second);
+    module.verify().unwrap();
+}
@@ -0,0 +1,45 @@
+extern crate llvm;
+use llvm::*;
+#[test]
+fn test_intrinsic_names() {
+    let ctx = Context::new();
+    let module = Module::new("intrinsics", &ctx);
+    let u8_t = Type::get::<u8>(&ctx);
+    assert_eq!(Intrinsic::Ctpop.get_name(&[Type::get::<u64>(&ctx)]), "llvm.ctpop.i64");
+    assert_eq!(Intrinsic::Sqrt.get_name(&[Type::get::<[f32; 4]>(&ctx)]), "llvm.sqrt.v4f32");
+    assert_eq!(Intrinsic::Bswap.get_name(&[Type::get::<[u16; 2]>(&ctx)]), "llvm.bswap.v2i16");
+    let shared_t = module.add_global_in_address_space("shared", u8_t, AddressSpace::Shared).get_type();
+    let byte_ptr_t = PointerType::new(u8_t);
+    let name = Intrinsic::Memcpy.get_name(&[shared_t, byte_ptr_t, Type::get::<u64>(&ctx)]);
+    assert_eq!(name, "llvm.memcpy.p3i8.p0i8.i64");
+    assert_eq!(Intrinsic::Trap.get_name(&[]), "llvm.trap");
+}
+
+#[test]
+#[should_panic]
+fn test_float_intrinsic_on_integers() {
+    let ctx = Context::new();
+    Intrinsic::Sqrt.get_name(&[Type::get::<i32>(&ctx)]);
+}
+
+#[test]
+#[should_panic]
+fn test_integer_intrinsic_on_floats() {
+    let ctx = Context::new();
+    let module = Module::new("intrinsics", &ctx);
+    module.get_intrinsic(Intrinsic::SAddWithOverflow, &[Type::get::<f64>(&ctx)]);
+}
+
+#[test]
+#[should_panic]
+fn test_memory_intrinsic_on_integers() {
+    let ctx = Context::new();
+    let u64_t = Type::get::<u64>(&ctx);
+    Intrinsic::Memset.get_name(&[u64_t, u64_t]);
+}
+
+#[test]
+#[should_panic]
+fn test_wrong_number_of_overloads() {
+    Intrinsic::Pow.get_name(&[]);
+}